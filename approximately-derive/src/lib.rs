@@ -0,0 +1,159 @@
+//! # approximately-derive
+//!
+//! Provide `#[derive(ApproxEq)]` for the `approximately` crate, enabled through its `derive` feature.
+#![deny(rustdoc::broken_intra_doc_links)]
+#![warn(missing_docs)]
+#![warn(clippy::all, clippy::nursery)]
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, spanned::Spanned, Data, DeriveInput, Expr, Field, Fields, Index, Type,
+};
+
+/// Field types accepted by `#[approx(tolerance = ...)]`.
+const NUMERIC_TYPES: &[&str] = &[
+    "f32", "f64", "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128",
+    "usize",
+];
+
+/// Derive `ApproxEq` by comparing every field with `approx`.
+///
+/// Every field must implement `ApproxEq`, except fields marked `#[approx(skip)]`,
+/// which are compared with `PartialEq` instead.
+/// Both named and tuple structs are supported.
+///
+/// A numeric field can use its own tolerance with `#[approx(tolerance = 0.01)]`;
+/// it is then compared with `ApproxTolerance::approx_with_tolerance`.
+/// The value may be any constant expression of the field's type.
+#[proc_macro_derive(ApproxEq, attributes(approx))]
+pub fn derive_approx_eq(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// How a single field takes part in the comparison.
+enum Compare {
+    Approx,
+    Exact,
+    Tolerance(Expr),
+}
+
+fn is_numeric(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => path
+            .path
+            .get_ident()
+            .is_some_and(|ident| NUMERIC_TYPES.iter().any(|name| ident == name)),
+        _ => false,
+    }
+}
+
+fn compare_of(field: &Field) -> syn::Result<Compare> {
+    let mut compare = Compare::Approx;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("approx"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if !matches!(compare, Compare::Approx) {
+                return Err(meta.error("only one of `skip` or `tolerance` may be given"));
+            }
+            if meta.path.is_ident("skip") {
+                compare = Compare::Exact;
+                Ok(())
+            } else if meta.path.is_ident("tolerance") {
+                if !is_numeric(&field.ty) {
+                    return Err(meta.error(format!(
+                        "`tolerance` is only supported on numeric fields ({})",
+                        NUMERIC_TYPES.join(", ")
+                    )));
+                }
+                compare = Compare::Tolerance(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported approx attribute, expected `skip` or `tolerance`"))
+            }
+        })?;
+    }
+    Ok(compare)
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new(
+                input.span(),
+                "ApproxEq can only be derived for structs",
+            ))
+        }
+    };
+    let members: Vec<TokenStream2> = match fields {
+        Fields::Named(named) => named
+            .named
+            .iter()
+            .map(|field| {
+                let ident = &field.ident;
+                quote!(#ident)
+            })
+            .collect(),
+        Fields::Unnamed(unnamed) => (0..unnamed.unnamed.len())
+            .map(|index| {
+                let index = Index::from(index);
+                quote!(#index)
+            })
+            .collect(),
+        Fields::Unit => Vec::new(),
+    };
+
+    let mut checks = Vec::new();
+    let mut bounds = Vec::new();
+    for (field, member) in fields.iter().zip(&members) {
+        let ty = &field.ty;
+        match compare_of(field)? {
+            Compare::Approx => {
+                checks
+                    .push(quote!(::approximately::ApproxEq::approx(&self.#member, &other.#member)));
+                bounds.push(quote!(#ty: ::approximately::ApproxEq));
+            }
+            Compare::Exact => {
+                checks.push(quote!(self.#member == other.#member));
+                bounds.push(quote!(#ty: ::core::cmp::PartialEq));
+            }
+            Compare::Tolerance(tolerance) => {
+                checks.push(
+                    quote!(::approximately::ApproxTolerance::approx_with_tolerance(
+                        &self.#member,
+                        &other.#member,
+                        #tolerance
+                    )),
+                );
+                bounds.push(quote!(#ty: ::approximately::ApproxTolerance));
+            }
+        }
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let mut predicates: Vec<TokenStream2> = where_clause
+        .map(|clause| clause.predicates.iter().map(|p| quote!(#p)).collect())
+        .unwrap_or_default();
+    predicates.push(quote!(Self: ::core::fmt::Debug));
+    predicates.extend(bounds);
+
+    Ok(quote! {
+        impl #impl_generics ::approximately::ApproxEq for #name #ty_generics
+        where
+            #(#predicates,)*
+        {
+            fn approx<__ApproxOther: ::core::borrow::Borrow<Self>>(&self, other: __ApproxOther) -> bool {
+                let other = ::core::borrow::Borrow::borrow(&other);
+                true #(&& #checks)*
+            }
+        }
+    })
+}
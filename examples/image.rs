@@ -1,4 +1,4 @@
-use approximately::ApproxEq;
+use approximately::{approx_at_least, ApproxEq};
 
 /// Assume this is an image structure.
 /// I need to ensure that at least 80% of the blocks in the images are the same to consider the two images identical.
@@ -7,13 +7,7 @@ struct Image(Vec<u8>);
 
 impl ApproxEq for Image {
     fn approx<T: std::borrow::Borrow<Self>>(&self, other: T) -> bool {
-        self.0
-            .iter()
-            .zip(other.borrow().0.iter())
-            .filter(|(a, b)| a == b)
-            .count() as f32
-            / self.0.len() as f32
-            >= 0.8
+        approx_at_least(&self.0, &other.borrow().0, 0.8)
     }
 }
 
@@ -0,0 +1,28 @@
+#![no_main]
+//! Compare arbitrary f32 values and check that no comparison panics and the basic laws hold.
+use approximately::{ApproxEq, ApproxFloat, ApproxTolerance};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (f32, f32, f32)| {
+    let (a, b, tol) = input;
+    if !a.is_nan() {
+        assert!(a.approx(a));
+        assert!(a.approx_with_tolerance(a, tol));
+        assert!(a.approx_relative(a));
+        assert!(a.approx_combined(a));
+        assert!(a.approx_ulps(a, 0));
+    }
+    assert_eq!(a.approx(b), b.approx(a));
+    assert_eq!(a.approx_with_tolerance(b, tol), b.approx_with_tolerance(a, tol));
+    assert_eq!(a.approx_relative(b), b.approx_relative(a));
+    if a.is_infinite() != b.is_infinite() {
+        assert!(!a.approx(b));
+        assert!(!a.approx_relative(b));
+        assert!(!a.approx_combined(b));
+    }
+    let _ = a.approx_combined(b);
+    let _ = a.approx_within_percent(b, tol);
+    let _ = a.approx_angle(b);
+    let _ = a.approx_ulps(b, u32::MAX);
+    let _ = a.approx_distance(b);
+});
@@ -0,0 +1,418 @@
+//! Implementations for the standard library collections.
+//!
+//! Everything here needs `alloc`, and the hashed collections need `std`.
+use crate::{approx_all, ApproxEq, ApproxTolerance};
+use alloc::collections::{BTreeMap, BinaryHeap, LinkedList, VecDeque};
+#[cfg(any(feature = "std", feature = "indexmap"))]
+use alloc::vec::Vec;
+use core::{borrow::Borrow, fmt::Debug};
+#[cfg(feature = "std")]
+use std::{
+    collections::{HashMap, HashSet},
+    hash::{BuildHasher, Hash},
+};
+
+/// Find the first key of `map` whose value is missing from or diverges in `other`.
+#[cfg(feature = "std")]
+fn hash_map_mismatch<'a, K, V, S>(
+    map: &'a HashMap<K, V, S>,
+    other: &HashMap<K, V, S>,
+    approx: impl Fn(&V, &V) -> bool,
+) -> Option<&'a K>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    map.iter()
+        .find(|(key, a)| other.get(*key).is_none_or(|b| !approx(a, b)))
+        .map(|(key, _)| key)
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> ApproxEq for HashMap<K, V, S>
+where
+    K: Eq + Hash + Debug + Clone,
+    V: ApproxEq + Debug + Clone,
+    S: BuildHasher,
+{
+    fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+        let other = other.borrow();
+        self.len() == other.len() && hash_map_mismatch(self, other, |a, b| a.approx(b)).is_none()
+    }
+    fn assert_approx<T: Borrow<Self> + Debug + Clone>(&self, other: T) {
+        let borrowed = other.borrow();
+        assert!(
+            self.len() == borrowed.len(),
+            "{self:?} != {other:?} (lengths {} and {})",
+            self.len(),
+            borrowed.len()
+        );
+        if let Some(key) = hash_map_mismatch(self, borrowed, |a, b| a.approx(b)) {
+            panic!("{self:?} != {other:?} (key {key:?} differs)");
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> ApproxTolerance for HashMap<K, V, S>
+where
+    K: Eq + Hash + Debug + Clone,
+    V: ApproxTolerance + Debug + Clone,
+    S: BuildHasher,
+{
+    type Tolerance = V::Tolerance;
+    const TOLERANCE: Self::Tolerance = V::TOLERANCE;
+    fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: Self::Tolerance) -> bool {
+        let other = other.borrow();
+        self.len() == other.len()
+            && hash_map_mismatch(self, other, |a, b| a.approx_with_tolerance(b, tol)).is_none()
+    }
+}
+
+/// Evaluate if every element of `set` has its own approximately equal partner in `other`.
+///
+/// Each element greedily takes the first unmatched partner, in `O(n * m)`.
+#[cfg(any(feature = "std", feature = "indexmap"))]
+#[allow(
+    clippy::redundant_pub_crate,
+    reason = "shared with the indexmap integration, not part of the API"
+)]
+pub(crate) fn set_matches<'a, A: 'a>(
+    mut set: impl ExactSizeIterator<Item = &'a A>,
+    other: impl ExactSizeIterator<Item = &'a A>,
+    approx: impl Fn(&A, &A) -> bool,
+) -> bool {
+    let mut unmatched: Vec<&A> = other.collect();
+    set.len() == unmatched.len()
+        && set.all(|a| {
+            unmatched
+                .iter()
+                .position(|b| approx(a, b))
+                .map(|index| unmatched.swap_remove(index))
+                .is_some()
+        })
+}
+
+/// Sets are approximately equal if their elements can be paired up one-to-one with approximately equal partners.
+///
+/// The matching compares every pair of elements, which is quadratic and meant for test-sized sets.
+/// It is also greedy, so when an element is approximately equal to several partners
+/// it may take the one another element needed, and sets that could be paired up are reported as different.
+#[cfg(feature = "std")]
+impl<A, S> ApproxEq for HashSet<A, S>
+where
+    A: ApproxEq + Debug + Clone,
+    S: BuildHasher,
+{
+    fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+        set_matches(self.iter(), other.borrow().iter(), |a, b| a.approx(b))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<A, S> ApproxTolerance for HashSet<A, S>
+where
+    A: ApproxTolerance + Debug + Clone,
+    S: BuildHasher,
+{
+    type Tolerance = A::Tolerance;
+    const TOLERANCE: Self::Tolerance = A::TOLERANCE;
+    fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: Self::Tolerance) -> bool {
+        set_matches(self.iter(), other.borrow().iter(), |a, b| {
+            a.approx_with_tolerance(b, tol)
+        })
+    }
+}
+
+/// A borrowed key-value pair of a map.
+type Entry<'a, K, V> = (&'a K, &'a V);
+
+/// Walk both maps in lockstep and return the first pair of entries whose keys or values diverge.
+fn btree_map_mismatch<'a, K, V>(
+    map: &'a BTreeMap<K, V>,
+    other: &'a BTreeMap<K, V>,
+    approx: impl Fn(&V, &V) -> bool,
+) -> Option<(Entry<'a, K, V>, Entry<'a, K, V>)>
+where
+    K: Ord,
+{
+    map.iter()
+        .zip(other.iter())
+        .find(|((key_a, a), (key_b, b))| key_a != key_b || !approx(a, b))
+}
+
+impl<K, V> ApproxEq for BTreeMap<K, V>
+where
+    K: Ord + Debug + Clone,
+    V: ApproxEq + Debug + Clone,
+{
+    fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+        let other = other.borrow();
+        self.len() == other.len() && btree_map_mismatch(self, other, |a, b| a.approx(b)).is_none()
+    }
+    fn assert_approx<T: Borrow<Self> + Debug + Clone>(&self, other: T) {
+        let borrowed = other.borrow();
+        match btree_map_mismatch(self, borrowed, |a, b| a.approx(b)) {
+            Some(((key_a, _), (key_b, _))) if key_a != key_b => {
+                panic!("{self:?} != {other:?} (key {key_a:?} != {key_b:?})")
+            }
+            Some(((key, a), (_, b))) => {
+                panic!("{self:?} != {other:?} (key {key:?}: {a:?} != {b:?})")
+            }
+            None => assert!(
+                self.len() == borrowed.len(),
+                "{self:?} != {other:?} (lengths {} and {})",
+                self.len(),
+                borrowed.len()
+            ),
+        }
+    }
+}
+
+impl<K, V> ApproxTolerance for BTreeMap<K, V>
+where
+    K: Ord + Debug + Clone,
+    V: ApproxTolerance + Debug + Clone,
+{
+    type Tolerance = V::Tolerance;
+    const TOLERANCE: Self::Tolerance = V::TOLERANCE;
+    fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: Self::Tolerance) -> bool {
+        let other = other.borrow();
+        self.len() == other.len()
+            && btree_map_mismatch(self, other, |a, b| a.approx_with_tolerance(b, tol)).is_none()
+    }
+}
+
+/// Lists are compared element by element from front to back.
+impl<A> ApproxEq for LinkedList<A>
+where
+    A: ApproxEq + Debug + Clone,
+{
+    fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+        let other = other.borrow();
+        self.len() == other.len() && approx_all(self.iter(), other.iter())
+    }
+}
+
+impl<A> ApproxTolerance for LinkedList<A>
+where
+    A: ApproxTolerance + Debug + Clone,
+{
+    type Tolerance = A::Tolerance;
+    const TOLERANCE: Self::Tolerance = A::TOLERANCE;
+    fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: Self::Tolerance) -> bool {
+        let other = other.borrow();
+        self.len() == other.len()
+            && self
+                .iter()
+                .zip(other.iter())
+                .all(|(a, b)| a.approx_with_tolerance(b, tol))
+    }
+}
+
+/// Deques are compared in their logical order, wherever the elements sit in the ring buffer.
+impl<A> ApproxEq for VecDeque<A>
+where
+    A: ApproxEq + Debug + Clone,
+{
+    fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+        approx_all(self.iter(), other.borrow().iter())
+    }
+}
+
+impl<A> ApproxTolerance for VecDeque<A>
+where
+    A: ApproxTolerance + Debug + Clone,
+{
+    type Tolerance = A::Tolerance;
+    const TOLERANCE: Self::Tolerance = A::TOLERANCE;
+    fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: Self::Tolerance) -> bool {
+        let other = other.borrow();
+        self.len() == other.len()
+            && self
+                .iter()
+                .zip(other.iter())
+                .all(|(a, b)| a.approx_with_tolerance(b, tol))
+    }
+}
+
+/// Heaps are compared by their sorted contents, so the order of insertion does not matter.
+///
+/// Each comparison clones and sorts both heaps, in `O(n log n)` time and `O(n)` extra memory.
+impl<A> ApproxEq for BinaryHeap<A>
+where
+    A: ApproxEq + Ord + Debug + Clone,
+{
+    fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+        self.clone()
+            .into_sorted_vec()
+            .approx(other.borrow().clone().into_sorted_vec())
+    }
+}
+
+impl<A> ApproxTolerance for BinaryHeap<A>
+where
+    A: ApproxTolerance + Ord + Debug + Clone,
+{
+    type Tolerance = A::Tolerance;
+    const TOLERANCE: Self::Tolerance = A::TOLERANCE;
+    fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: Self::Tolerance) -> bool {
+        self.clone()
+            .into_sorted_vec()
+            .approx_with_tolerance(other.borrow().clone().into_sorted_vec(), tol)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::ops::Not;
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_approx_hash_map() {
+        let a = HashMap::from([("x".to_owned(), 1.0f64), ("y".to_owned(), 2.0)]);
+        let b = HashMap::from([("y".to_owned(), 2.0000001f64), ("x".to_owned(), 1.0)]);
+        assert!(a.approx(&b));
+        assert!(a.approx(HashMap::from([("x".to_owned(), 1.0f64)])).not());
+        assert!(a
+            .approx(HashMap::from([
+                ("x".to_owned(), 1.0f64),
+                ("z".to_owned(), 2.0)
+            ]))
+            .not());
+        assert!(a
+            .approx(HashMap::from([
+                ("x".to_owned(), 1.0f64),
+                ("y".to_owned(), 2.1)
+            ]))
+            .not());
+        assert!(a.approx_with_tolerance(
+            HashMap::from([("x".to_owned(), 1.0f64), ("y".to_owned(), 2.1)]),
+            0.2
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    #[should_panic(expected = "(key \"y\" differs)")]
+    fn test_assert_not_approx_hash_map() {
+        let a = HashMap::from([("x", 1.0f64), ("y", 2.0)]);
+        a.assert_approx(HashMap::from([("x", 1.0f64), ("y", 2.1)]));
+    }
+
+    #[test]
+    fn test_approx_btree_map() {
+        let a = BTreeMap::from([
+            ("a".to_owned(), vec![1.0f64, 2.0]),
+            ("b".to_owned(), vec![3.0]),
+        ]);
+        let b = BTreeMap::from([
+            ("a".to_owned(), vec![1.0000001f64, 2.0]),
+            ("b".to_owned(), vec![3.0]),
+        ]);
+        assert!(a.approx(&b));
+        assert!(a
+            .approx(BTreeMap::from([
+                ("a".to_owned(), vec![1.0f64, 2.0]),
+                ("c".to_owned(), vec![3.0]),
+            ]))
+            .not());
+        assert!(a
+            .approx(BTreeMap::from([
+                ("a".to_owned(), vec![1.0f64, 2.0]),
+                ("b".to_owned(), vec![3.0, 4.0]),
+            ]))
+            .not());
+        assert!(a
+            .approx(BTreeMap::from([("a".to_owned(), vec![1.0f64, 2.0])]))
+            .not());
+
+        let nested = BTreeMap::from([("outer", a)]);
+        assert!(nested.approx(BTreeMap::from([("outer", b)])));
+    }
+
+    #[test]
+    #[should_panic(expected = "(key \"b\": [3.0] != [3.1])")]
+    fn test_assert_not_approx_btree_map() {
+        let a = BTreeMap::from([("a", vec![1.0f64]), ("b", vec![3.0])]);
+        a.assert_approx(BTreeMap::from([("a", vec![1.0f64]), ("b", vec![3.1])]));
+    }
+
+    #[test]
+    #[should_panic(expected = "(key \"b\" != \"c\")")]
+    fn test_assert_not_approx_btree_map_keys() {
+        let a = BTreeMap::from([("a", 1.0f64), ("b", 3.0)]);
+        a.assert_approx(BTreeMap::from([("a", 1.0f64), ("c", 3.0)]));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_approx_hash_set() {
+        // Floats are not `Hash`, so perturb integers within a tolerance instead.
+        let set = |values: &[i64]| values.iter().copied().collect::<HashSet<i64>>();
+        let a = set(&[10, 20, 30]);
+        assert!(a.approx(set(&[30, 10, 20])));
+        assert!(a.approx_with_tolerance(set(&[31, 9, 20]), 1));
+        assert!(a.approx_with_tolerance(set(&[32, 9, 20]), 1).not());
+        assert!(a.approx(set(&[10, 20])).not());
+        // Both 10 and 11 are within the tolerance of 10 only, so one of them stays unmatched.
+        assert!(set(&[10, 11])
+            .approx_with_tolerance(set(&[10, 13]), 1)
+            .not());
+        assert!(set(&[]).approx(set(&[])));
+    }
+
+    #[test]
+    fn test_approx_linked_list() {
+        let mut front = LinkedList::new();
+        for value in [3.0f64, 2.0, 1.0] {
+            front.push_front(value);
+        }
+        let mut back = LinkedList::new();
+        for value in [1.0f64, 2.0, 3.0000001] {
+            back.push_back(value);
+        }
+        assert!(front.approx(&back));
+        assert!(back.approx(&front));
+        back.push_back(4.0);
+        assert!(front.approx(&back).not());
+        back.pop_back();
+        back.pop_front();
+        back.push_back(1.0);
+        assert!(front.approx(&back).not());
+        assert!(LinkedList::from([1.0f32, 2.0])
+            .approx_with_tolerance(LinkedList::from([1.1, 2.0]), 0.2));
+    }
+
+    #[test]
+    fn test_approx_vec_deque() {
+        // Rotate the first deque so that its elements wrap around the end of the buffer.
+        let mut a = VecDeque::with_capacity(4);
+        a.extend([0.0f32, 0.0, 1.0, 2.0]);
+        a.pop_front();
+        a.pop_front();
+        a.push_back(3.0);
+        a.push_back(4.0);
+        assert!(a.as_slices().1.is_empty().not());
+        let b = VecDeque::from([1.0001f32, 2.0, 3.0, 4.0]);
+        assert!(a.approx(&b));
+        assert!(b.approx(&a));
+        assert!(a.approx(VecDeque::from([2.0f32, 1.0, 3.0, 4.0])).not());
+        assert!(a.approx(VecDeque::from([1.0f32, 2.0, 3.0])).not());
+        assert!(a.approx_with_tolerance(VecDeque::from([1.1f32, 2.0, 3.0, 4.0]), 0.2));
+    }
+
+    #[test]
+    fn test_approx_binary_heap() {
+        let a: BinaryHeap<i32> = [3, 1, 4, 1, 5].into_iter().collect();
+        let mut b = BinaryHeap::new();
+        for value in [5, 1, 1, 4, 3] {
+            b.push(value);
+        }
+        assert!(a.approx(&b));
+        assert!(a.approx(BinaryHeap::from([3, 1, 4, 1])).not());
+        assert!(a.approx(BinaryHeap::from([3, 1, 4, 2, 5])).not());
+        assert!(a.approx_with_tolerance(BinaryHeap::from([2, 2, 4, 1, 6]), 1));
+    }
+}
@@ -0,0 +1,87 @@
+//! Implementations for [`arrayvec::ArrayVec`].
+use crate::{ApproxEq, ApproxTolerance};
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use arrayvec::ArrayVec;
+use core::{borrow::Borrow, fmt::Debug};
+
+/// Compare the filled elements like slices, so vectors of different lengths are never approximately equal.
+impl<A, const CAP: usize> ApproxEq for ArrayVec<A, CAP>
+where
+    A: ApproxEq + Debug + Clone,
+{
+    fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+        self[..].approx(&other.borrow()[..])
+    }
+    fn assert_approx<T: Borrow<Self> + Debug + Clone>(&self, other: T) {
+        self[..].assert_approx(&other.borrow()[..]);
+    }
+    fn first_mismatch_index<T: Borrow<Self>>(&self, other: T) -> Option<usize> {
+        self[..].first_mismatch_index(&other.borrow()[..])
+    }
+    #[cfg(feature = "alloc")]
+    fn first_mismatch<T: Borrow<Self>>(&self, other: T) -> Option<String> {
+        self[..].first_mismatch(&other.borrow()[..])
+    }
+    fn approx_distance<T: Borrow<Self>>(&self, other: T) -> f64 {
+        self[..].approx_distance(&other.borrow()[..])
+    }
+}
+
+impl<A, const CAP: usize> ApproxTolerance for ArrayVec<A, CAP>
+where
+    A: ApproxTolerance + Debug + Clone,
+{
+    type Tolerance = A::Tolerance;
+    const TOLERANCE: Self::Tolerance = A::TOLERANCE;
+    fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: Self::Tolerance) -> bool {
+        self[..].approx_with_tolerance(&other.borrow()[..], tol)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::ops::Not;
+
+    #[test]
+    fn test_approx_array_vec() {
+        let a: ArrayVec<f32, 8> = [1.0, 2.0, 3.0].into_iter().collect();
+        let mut b = ArrayVec::<f32, 8>::new();
+        b.extend([1.0001, 2.0, 3.0]);
+        assert_eq!((a.len(), a.capacity()), (3, 8));
+        assert!(a.approx(&b));
+        a.assert_approx(&b);
+        assert!(a.approx_with_tolerance(&b, 0.0).not());
+        b.push(4.0);
+        assert!(a.approx(&b).not());
+        b.pop();
+        b[2] = 3.1;
+        assert!(a.approx(&b).not());
+        assert!(a.approx_with_tolerance(&b, 0.2));
+    }
+
+    #[test]
+    fn test_array_vec_mismatch() {
+        let a: ArrayVec<f64, 4> = [1.0, 2.0, 3.0].into_iter().collect();
+        let b: ArrayVec<f64, 4> = [1.0, 2.0, 3.5].into_iter().collect();
+        assert_eq!(a.first_mismatch_index(&a), None);
+        assert_eq!(a.first_mismatch_index(&b), Some(2));
+        assert_eq!(a.approx_distance(&b), 0.5);
+        #[cfg(feature = "alloc")]
+        {
+            let nested: ArrayVec<ArrayVec<f64, 4>, 2> =
+                [a.clone(), a.clone()].into_iter().collect();
+            let other: ArrayVec<ArrayVec<f64, 4>, 2> = [a, b].into_iter().collect();
+            assert_eq!(nested.first_mismatch(other).as_deref(), Some("[1][2]"));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "slices differ at index 1")]
+    fn test_assert_array_vec() {
+        let a: ArrayVec<f32, 8> = [1.0, 2.0].into_iter().collect();
+        let b: ArrayVec<f32, 8> = [1.0, 2.5].into_iter().collect();
+        a.assert_approx(&b);
+    }
+}
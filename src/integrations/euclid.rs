@@ -0,0 +1,64 @@
+//! Implementations for the geometry types of [`euclid`].
+use crate::{ApproxEq, ApproxTolerance};
+use core::borrow::Borrow;
+use euclid::{Point2D, Point3D, Size2D, Size3D, Vector2D, Vector3D};
+
+macro_rules! impl_euclid {
+    ($($euclid:ident { $($field:ident),+ })*) => {$(
+        /// The fields are compared separately, and all must be approximately equal.
+        /// The unit is a marker and not compared, as both sides share it.
+        impl<A: ApproxEq, U> ApproxEq for $euclid<A, U> {
+            fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+                let other = other.borrow();
+                $(self.$field.approx(&other.$field))&&+
+            }
+        }
+
+        impl<A: ApproxTolerance, U> ApproxTolerance for $euclid<A, U> {
+            type Tolerance = A::Tolerance;
+            const TOLERANCE: Self::Tolerance = A::TOLERANCE;
+            fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: Self::Tolerance) -> bool {
+                let other = other.borrow();
+                $(self.$field.approx_with_tolerance(&other.$field, tol))&&+
+            }
+        }
+    )*};
+}
+
+impl_euclid! {
+    Point2D { x, y }
+    Point3D { x, y, z }
+    Vector2D { x, y }
+    Vector3D { x, y, z }
+    Size2D { width, height }
+    Size3D { width, height, depth }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::ops::Not;
+    use euclid::{point2, size2, vec3, UnknownUnit};
+
+    #[test]
+    fn test_approx_point2d() {
+        let a: Point2D<f32, UnknownUnit> = point2(1.0, 2.0);
+        assert!(a.approx(point2(1.0005, 2.0)));
+        assert!(a.approx(point2(1.0, 2.0009)));
+        assert!(a.approx(point2(1.002, 2.0)).not());
+        assert!(a.approx(point2(1.0, 2.002)).not());
+        assert!(a.approx_with_tolerance(point2(1.002, 2.0), 1e-2));
+    }
+
+    #[test]
+    fn test_approx_euclid_3d_and_sizes() {
+        struct Pixels;
+        let v: Vector3D<f64, Pixels> = vec3(1.0, 2.0, 3.0);
+        assert!(v.approx(vec3(1.0, 2.0, 3.0000001)));
+        assert!(v.approx(vec3(1.0, 2.0, 3.1)).not());
+        let size: Size2D<u32, Pixels> = size2(640, 480);
+        assert!(size.approx(size2(640, 480)));
+        assert!(size.approx_with_tolerance(size2(641, 479), 1));
+        assert!(size.approx(size2(640, 481)).not());
+    }
+}
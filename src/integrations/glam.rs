@@ -0,0 +1,108 @@
+//! Implementations for the vector, quaternion and matrix types of [`glam`].
+use crate::{ApproxEq, ApproxTolerance};
+use core::borrow::Borrow;
+use glam::{
+    DMat2, DMat3, DMat4, DQuat, DVec2, DVec3, DVec4, Mat2, Mat3, Mat3A, Mat4, Quat, Vec2, Vec3,
+    Vec3A, Vec4,
+};
+
+/// Compare quaternions as rotations.
+pub trait ApproxRotation: ApproxEq {
+    /// Evaluate if the quaternions represent approximately the same rotation.
+    ///
+    /// `q` and `-q` describe the same rotation, so a quaternion is also equal to the negation of an approximately equal one.
+    fn approx_rotation<T: Borrow<Self>>(&self, other: T) -> bool;
+}
+
+macro_rules! impl_glam {
+    ($($glam:ty => $scalar:ty, $to_array:ident);* $(;)?) => {$(
+        /// The components are compared separately, and all must be approximately equal.
+        impl ApproxEq for $glam {
+            fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+                self.$to_array().approx(other.borrow().$to_array())
+            }
+        }
+
+        impl ApproxTolerance for $glam {
+            type Tolerance = $scalar;
+            const TOLERANCE: $scalar = <$scalar>::TOLERANCE;
+            fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: $scalar) -> bool {
+                self.$to_array().approx_with_tolerance(other.borrow().$to_array(), tol)
+            }
+        }
+    )*};
+}
+
+impl_glam! {
+    Vec2 => f32, to_array;
+    Vec3 => f32, to_array;
+    Vec3A => f32, to_array;
+    Vec4 => f32, to_array;
+    Quat => f32, to_array;
+    Mat2 => f32, to_cols_array;
+    Mat3 => f32, to_cols_array;
+    Mat3A => f32, to_cols_array;
+    Mat4 => f32, to_cols_array;
+    DVec2 => f64, to_array;
+    DVec3 => f64, to_array;
+    DVec4 => f64, to_array;
+    DQuat => f64, to_array;
+    DMat2 => f64, to_cols_array;
+    DMat3 => f64, to_cols_array;
+    DMat4 => f64, to_cols_array;
+}
+
+macro_rules! impl_rotation {
+    ($($quat:ty),*) => {$(
+        impl ApproxRotation for $quat {
+            fn approx_rotation<T: Borrow<Self>>(&self, other: T) -> bool {
+                let other = *other.borrow();
+                self.approx(other) || self.approx(-other)
+            }
+        }
+    )*};
+}
+
+impl_rotation!(Quat, DQuat);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::ops::Not;
+
+    #[test]
+    fn test_approx_vec3() {
+        let a = Vec3::new(1.0, 2.0, 3.0);
+        assert!(a.approx(Vec3::new(1.0, 2.0005, 3.0)));
+        assert!(a.approx(Vec3::new(1.0, 2.002, 3.0)).not());
+        assert!(a.approx_with_tolerance(Vec3::new(1.0, 2.002, 3.0), 1e-2));
+        assert!(DVec3::ONE.approx(DVec3::splat(1.0000001)));
+        assert!(Vec4::ONE.approx(Vec4::new(1.0, 1.0, 1.0, 1.01)).not());
+    }
+
+    #[test]
+    fn test_approx_mat4() {
+        let a = Mat4::from_rotation_z(0.5);
+        assert!(a.approx(Mat4::from_rotation_z(0.5001)));
+        assert!(a.approx(Mat4::from_rotation_z(0.51)).not());
+        assert!(a.approx(a.transpose()).not());
+    }
+
+    #[test]
+    fn test_approx_quat() {
+        let q = Quat::from_rotation_y(1.0);
+        assert!(q.approx(Quat::from_rotation_y(1.0001)));
+        assert!(q.approx(-q).not());
+        assert!(q.approx_rotation(-q));
+        assert!(q.approx_rotation(-Quat::from_rotation_y(1.0001)));
+        assert!(q.approx_rotation(Quat::from_rotation_x(1.0)).not());
+        let d = DQuat::from_rotation_z(2.0);
+        assert!(d.approx_rotation(-d));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_assert_not_approx_vec3() {
+        Vec3::ZERO.assert_approx(Vec3::new(0.0, 0.0, 0.01));
+    }
+}
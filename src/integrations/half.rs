@@ -0,0 +1,80 @@
+//! Implementations for [`half::f16`] and [`half::bf16`].
+//!
+//! [`f16`] has 3 to 4 significant decimal digits and defaults to an absolute tolerance of `1e-2`.
+//! [`bf16`] keeps the range of [`f32`] but only has 2 to 3 digits, so its spacing exceeds any fixed
+//! absolute tolerance for large values: [`ApproxEq::approx`] allows `1e-2` of the larger magnitude instead,
+//! or the absolute `1e-2` near zero.
+//! The values are widened to [`f32`] before subtracting so no precision is lost in the difference.
+use crate::{ApproxEq, ApproxTolerance};
+use core::borrow::Borrow;
+use half::{bf16, f16};
+
+macro_rules! impl_half {
+    ($($half:ty),*) => {$(
+        impl ApproxTolerance for $half {
+            type Tolerance = Self;
+            const TOLERANCE: Self = Self::from_f32_const(1e-2);
+            fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: Self) -> bool {
+                self.to_f32()
+                    .approx_with_tolerance(other.borrow().to_f32(), tol.to_f32())
+            }
+        }
+    )*};
+}
+
+impl_half!(f16, bf16);
+
+impl ApproxEq for f16 {
+    fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+        self.approx_with_tolerance(other, Self::TOLERANCE)
+    }
+}
+
+/// The relative tolerance of [`bf16`], about one step of its 8-bit mantissa.
+const BF16_RELATIVE_TOLERANCE: f32 = 1e-2;
+
+impl ApproxEq for bf16 {
+    fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+        let (this, other) = (self.to_f32(), other.borrow().to_f32());
+        let relative = this.abs().max(other.abs()) * BF16_RELATIVE_TOLERANCE;
+        this.approx_with_tolerance(other, relative.max(Self::TOLERANCE.to_f32()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::ops::Not;
+
+    #[test]
+    fn test_approx_half() {
+        let a = [f16::from_f32(1.0); 4];
+        assert!(a.approx([f16::from_f32(1.005); 4]));
+        assert!(a.approx([f16::from_f32(1.05); 4]).not());
+        assert!(f16::INFINITY.approx(f16::INFINITY));
+        assert!(f16::NAN.approx(f16::NAN).not());
+        assert!(f16::from_f32(1.0).approx_with_tolerance(f16::from_f32(1.05), f16::from_f32(0.1)));
+
+        let b = [bf16::from_f32(100.0); 4];
+        assert!(b.approx([bf16::from_f32(100.0); 4]));
+        assert!(bf16::from_f32(0.5).approx(bf16::from_f32(0.503)));
+        assert!(b.approx([bf16::from_f32(100.5); 4]));
+        assert!(b.approx([bf16::from_f32(102.0); 4]).not());
+        assert!(bf16::from_f32(0.0).approx(bf16::from_f32(0.005)));
+        assert!(bf16::from_f32(0.0).approx(bf16::from_f32(0.02)).not());
+    }
+
+    #[test]
+    fn test_approx_bf16_spacing() {
+        // Neighbouring bf16 values are 4 apart here, far above the absolute tolerance.
+        let a = bf16::from_f32(1000.0);
+        let next = bf16::from_bits(a.to_bits() + 1);
+        assert_eq!(next.to_f32() - a.to_f32(), 4.0);
+        assert!(a.approx(next));
+        assert!(a.approx_with_tolerance(next, bf16::TOLERANCE).not());
+        assert!(a.approx(bf16::from_f32(1016.0)).not());
+        assert!(bf16::INFINITY.approx(bf16::INFINITY));
+        assert!(bf16::MAX.approx(bf16::INFINITY).not());
+        assert!(bf16::NAN.approx(bf16::NAN).not());
+    }
+}
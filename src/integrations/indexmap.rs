@@ -0,0 +1,134 @@
+//! Implementations for [`indexmap::IndexMap`] and [`indexmap::IndexSet`].
+use crate::{collections::set_matches, ApproxEq, ApproxTolerance};
+use core::{
+    borrow::Borrow,
+    fmt::Debug,
+    hash::{BuildHasher, Hash},
+};
+use indexmap::{IndexMap, IndexSet};
+
+/// Find the first key of `map`, in insertion order, whose value is missing from or diverges in `other`.
+fn index_map_mismatch<'a, K, V, S>(
+    map: &'a IndexMap<K, V, S>,
+    other: &IndexMap<K, V, S>,
+    approx: impl Fn(&V, &V) -> bool,
+) -> Option<&'a K>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    map.iter()
+        .find(|(key, a)| other.get(*key).is_none_or(|b| !approx(a, b)))
+        .map(|(key, _)| key)
+}
+
+/// Maps are approximately equal if they have the same keys with approximately equal values, in any order.
+impl<K, V, S> ApproxEq for IndexMap<K, V, S>
+where
+    K: Eq + Hash + Debug + Clone,
+    V: ApproxEq + Debug + Clone,
+    S: BuildHasher,
+{
+    fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+        let other = other.borrow();
+        self.len() == other.len() && index_map_mismatch(self, other, |a, b| a.approx(b)).is_none()
+    }
+    fn assert_approx<T: Borrow<Self> + Debug + Clone>(&self, other: T) {
+        let borrowed = other.borrow();
+        assert!(
+            self.len() == borrowed.len(),
+            "{self:?} != {other:?} (lengths {} and {})",
+            self.len(),
+            borrowed.len()
+        );
+        if let Some(key) = index_map_mismatch(self, borrowed, |a, b| a.approx(b)) {
+            panic!("{self:?} != {other:?} (key {key:?} differs)");
+        }
+    }
+}
+
+impl<K, V, S> ApproxTolerance for IndexMap<K, V, S>
+where
+    K: Eq + Hash + Debug + Clone,
+    V: ApproxTolerance + Debug + Clone,
+    S: BuildHasher,
+{
+    type Tolerance = V::Tolerance;
+    const TOLERANCE: Self::Tolerance = V::TOLERANCE;
+    fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: Self::Tolerance) -> bool {
+        let other = other.borrow();
+        self.len() == other.len()
+            && index_map_mismatch(self, other, |a, b| a.approx_with_tolerance(b, tol)).is_none()
+    }
+}
+
+/// Sets are approximately equal if their elements can be paired up one-to-one, like `HashSet`.
+impl<A, S> ApproxEq for IndexSet<A, S>
+where
+    A: ApproxEq + Debug + Clone,
+    S: BuildHasher,
+{
+    fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+        set_matches(self.iter(), other.borrow().iter(), |a, b| a.approx(b))
+    }
+}
+
+impl<A, S> ApproxTolerance for IndexSet<A, S>
+where
+    A: ApproxTolerance + Debug + Clone,
+    S: BuildHasher,
+{
+    type Tolerance = A::Tolerance;
+    const TOLERANCE: Self::Tolerance = A::TOLERANCE;
+    fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: Self::Tolerance) -> bool {
+        set_matches(self.iter(), other.borrow().iter(), |a, b| {
+            a.approx_with_tolerance(b, tol)
+        })
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use core::ops::Not;
+
+    #[test]
+    fn test_approx_index_map() {
+        let a: IndexMap<&str, f64> = [("x", 1.0), ("y", 2.0), ("z", 3.0)].into_iter().collect();
+        let reordered: IndexMap<&str, f64> = [("z", 3.0000001), ("x", 1.0), ("y", 2.0)]
+            .into_iter()
+            .collect();
+        assert!(a.approx(&reordered));
+        assert!(reordered.approx(&a));
+        a.assert_approx(&reordered);
+
+        let mut changed = reordered.clone();
+        changed.insert("y", 2.1);
+        assert!(a.approx(&changed).not());
+        assert!(a.approx_with_tolerance(&changed, 0.2));
+        changed.insert("w", 0.0);
+        assert!(a.approx_with_tolerance(&changed, 0.2).not());
+    }
+
+    #[test]
+    #[should_panic(expected = "(key \"y\" differs)")]
+    fn test_assert_index_map_first_key() {
+        let a: IndexMap<&str, f64> = [("x", 1.0), ("y", 2.0), ("z", 3.0)].into_iter().collect();
+        let b: IndexMap<&str, f64> = [("z", 3.5), ("y", 2.5), ("x", 1.0)].into_iter().collect();
+        a.assert_approx(&b);
+    }
+
+    #[test]
+    fn test_approx_index_set() {
+        let a: IndexSet<i32> = [1, 5, 9].into_iter().collect();
+        let reordered: IndexSet<i32> = [9, 1, 5].into_iter().collect();
+        assert!(a.approx(&reordered));
+        assert!(a.approx_with_tolerance([10, 2, 4].into_iter().collect::<IndexSet<i32>>(), 1));
+        assert!(a
+            .approx_with_tolerance([10, 2, 3].into_iter().collect::<IndexSet<i32>>(), 1)
+            .not());
+        assert!(a
+            .approx([1, 5].into_iter().collect::<IndexSet<i32>>())
+            .not());
+    }
+}
@@ -0,0 +1,61 @@
+//! Implementations for the interchange types of [`mint`].
+use crate::{ApproxEq, ApproxTolerance};
+use core::borrow::Borrow;
+use mint::{Point2, Point3, Quaternion, Vector2, Vector3, Vector4};
+
+macro_rules! impl_mint {
+    ($($mint:ident { $($field:ident),+ })*) => {$(
+        /// The components are compared separately, and all must be approximately equal.
+        impl<A: ApproxEq> ApproxEq for $mint<A> {
+            fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+                let other = other.borrow();
+                $(self.$field.approx(&other.$field))&&+
+            }
+        }
+
+        impl<A: ApproxTolerance> ApproxTolerance for $mint<A> {
+            type Tolerance = A::Tolerance;
+            const TOLERANCE: Self::Tolerance = A::TOLERANCE;
+            fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: Self::Tolerance) -> bool {
+                let other = other.borrow();
+                $(self.$field.approx_with_tolerance(&other.$field, tol))&&+
+            }
+        }
+    )*};
+}
+
+impl_mint! {
+    Point2 { x, y }
+    Point3 { x, y, z }
+    Vector2 { x, y }
+    Vector3 { x, y, z }
+    Vector4 { x, y, z, w }
+    Quaternion { v, s }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::ops::Not;
+
+    #[test]
+    fn test_approx_vector3() {
+        let a = Vector3::from([1.0f32, 2.0, 3.0]);
+        assert!(a.approx(Vector3::from([1.0, 2.0005, 3.0])));
+        assert!(a.approx(Vector3::from([1.0, 2.002, 3.0])).not());
+        assert!(a.approx_with_tolerance(Vector3::from([1.0, 2.002, 3.0]), 1e-2));
+        assert!(a.approx(Vector3::from([1.0, 2.0, 3.1])).not());
+        assert!(Point2::from([1.0f64, 2.0]).approx(Point2::from([1.0000001, 2.0])));
+        assert!(Vector4::from([0u8, 1, 2, 3])
+            .approx(Vector4::from([0, 1, 2, 4]))
+            .not());
+    }
+
+    #[test]
+    fn test_approx_quaternion() {
+        let q = Quaternion::from([0.0f32, 0.0, 0.0, 1.0]);
+        assert!(q.approx(Quaternion::from([0.0, 0.0005, 0.0, 1.0])));
+        assert!(q.approx(Quaternion::from([0.0, 0.0, 0.0, 0.99])).not());
+        assert!(q.approx_with_tolerance(Quaternion::from([0.0, 0.0, 0.0, 0.99]), 0.02));
+    }
+}
@@ -0,0 +1,25 @@
+//! Implementations for types of third-party crates, each behind a feature of the same name.
+#[cfg(feature = "arrayvec")]
+pub mod arrayvec;
+#[cfg(feature = "euclid")]
+pub mod euclid;
+#[cfg(feature = "glam")]
+pub mod glam;
+#[cfg(feature = "half")]
+pub mod half;
+#[cfg(feature = "indexmap")]
+pub mod indexmap;
+#[cfg(feature = "mint")]
+pub mod mint;
+#[cfg(feature = "nalgebra")]
+pub mod nalgebra;
+#[cfg(feature = "ndarray")]
+pub mod ndarray;
+#[cfg(feature = "num-complex")]
+pub mod num_complex;
+#[cfg(feature = "ordered-float")]
+pub mod ordered_float;
+#[cfg(feature = "rust-decimal")]
+pub mod rust_decimal;
+#[cfg(feature = "smallvec")]
+pub mod smallvec;
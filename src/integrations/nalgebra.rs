@@ -0,0 +1,103 @@
+//! Implementations for [`nalgebra::Matrix`], which also covers its vectors.
+use crate::{ApproxEq, ApproxTolerance};
+use core::{borrow::Borrow, fmt::Debug};
+use nalgebra::{Dim, Matrix, RawStorage};
+
+/// Find the `(row, column)` of the first entry that diverges, in column-major order.
+fn matrix_mismatch<A, R, C, S>(
+    matrix: &Matrix<A, R, C, S>,
+    other: &Matrix<A, R, C, S>,
+    approx: impl Fn(&A, &A) -> bool,
+) -> Option<(usize, usize)>
+where
+    R: Dim,
+    C: Dim,
+    S: RawStorage<A, R, C>,
+{
+    let (rows, _) = matrix.shape();
+    matrix
+        .iter()
+        .zip(other.iter())
+        .position(|(a, b)| !approx(a, b))
+        .map(|index| (index % rows, index / rows))
+}
+
+/// Matrices of different dimensions are never approximately equal, otherwise the entries are compared one by one.
+impl<A, R, C, S> ApproxEq for Matrix<A, R, C, S>
+where
+    A: ApproxEq,
+    R: Dim,
+    C: Dim,
+    S: RawStorage<A, R, C> + Debug,
+{
+    fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+        let other = other.borrow();
+        self.shape() == other.shape() && matrix_mismatch(self, other, |a, b| a.approx(b)).is_none()
+    }
+    fn assert_approx<T: Borrow<Self> + Debug + Clone>(&self, other: T) {
+        let borrowed = other.borrow();
+        assert!(
+            self.shape() == borrowed.shape(),
+            "{self:?} != {other:?} (shapes {:?} and {:?})",
+            self.shape(),
+            borrowed.shape()
+        );
+        if let Some((row, column)) = matrix_mismatch(self, borrowed, |a, b| a.approx(b)) {
+            panic!(
+                "{self:?} != {other:?} (entry ({row}, {column}): {:?} != {:?})",
+                self[(row, column)],
+                borrowed[(row, column)]
+            );
+        }
+    }
+}
+
+impl<A, R, C, S> ApproxTolerance for Matrix<A, R, C, S>
+where
+    A: ApproxTolerance,
+    R: Dim,
+    C: Dim,
+    S: RawStorage<A, R, C> + Debug,
+{
+    type Tolerance = A::Tolerance;
+    const TOLERANCE: Self::Tolerance = A::TOLERANCE;
+    fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: Self::Tolerance) -> bool {
+        let other = other.borrow();
+        self.shape() == other.shape()
+            && matrix_mismatch(self, other, |a, b| a.approx_with_tolerance(b, tol)).is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::ops::Not;
+    use nalgebra::{Matrix4, Vector3};
+
+    #[test]
+    fn test_approx_vector3() {
+        let a = Vector3::new(1.0f64, 2.0, 3.0);
+        assert!(a.approx(Vector3::new(1.0, 2.0000001, 3.0)));
+        assert!(a.approx(Vector3::new(1.0, 2.00001, 3.0)).not());
+        assert!(a.approx_with_tolerance(Vector3::new(1.0, 2.00001, 3.0), 1e-4));
+    }
+
+    #[test]
+    fn test_approx_matrix4() {
+        let a = Matrix4::<f32>::identity();
+        let mut b = a;
+        b[(2, 1)] += 1e-4;
+        assert!(a.approx(b));
+        b[(2, 1)] += 1e-2;
+        assert!(a.approx(b).not());
+    }
+
+    #[test]
+    #[should_panic(expected = "(entry (2, 1): 0.0 != 0.01)")]
+    fn test_assert_not_approx_matrix4() {
+        let a = Matrix4::<f32>::identity();
+        let mut b = a;
+        b[(2, 1)] = 0.01;
+        a.assert_approx(b);
+    }
+}
@@ -0,0 +1,70 @@
+//! Implementations for [`ndarray::ArrayBase`], covering owned arrays and views.
+use crate::{ApproxEq, ApproxTolerance};
+use core::{borrow::Borrow, fmt::Debug};
+use ndarray::{ArrayBase, Data, Dimension};
+
+/// Arrays of different shapes are never approximately equal, otherwise the elements are compared in logical order.
+impl<A, S, D> ApproxEq for ArrayBase<S, D>
+where
+    A: ApproxEq,
+    S: Data<Elem = A>,
+    D: Dimension,
+{
+    fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+        let other = other.borrow();
+        self.shape() == other.shape() && self.iter().zip(other.iter()).all(|(a, b)| a.approx(b))
+    }
+    fn assert_approx<T: Borrow<Self> + Debug + Clone>(&self, other: T) {
+        let borrowed = other.borrow();
+        assert!(
+            self.shape() == borrowed.shape(),
+            "{self:?} != {other:?} (shapes {:?} and {:?})",
+            self.shape(),
+            borrowed.shape()
+        );
+        assert!(self.approx(borrowed), "{self:?} != {other:?}");
+    }
+}
+
+impl<A, S, D> ApproxTolerance for ArrayBase<S, D>
+where
+    A: ApproxTolerance,
+    S: Data<Elem = A>,
+    D: Dimension,
+{
+    type Tolerance = A::Tolerance;
+    const TOLERANCE: Self::Tolerance = A::TOLERANCE;
+    fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: Self::Tolerance) -> bool {
+        let other = other.borrow();
+        self.shape() == other.shape()
+            && self
+                .iter()
+                .zip(other.iter())
+                .all(|(a, b)| a.approx_with_tolerance(b, tol))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::ops::Not;
+    use ndarray::{array, Array2};
+
+    #[test]
+    fn test_approx_ndarray() {
+        let a: Array2<f64> = array![[1.0, 2.0], [3.0, 4.0]];
+        assert!(a.approx(array![[1.0000001, 2.0], [3.0, 4.0]]));
+        assert!(a.approx(array![[1.0, 2.0], [3.0, 4.1]]).not());
+        assert!(a.approx(array![[1.0, 2.0, 3.0, 4.0]]).not());
+        assert!(a.view().approx(a.t().t()));
+        assert!(a.t().approx(array![[1.0, 3.0], [2.0, 4.0]].view()));
+        assert!(a.approx_with_tolerance(array![[1.0, 2.0], [3.0, 4.1]], 0.2));
+    }
+
+    #[test]
+    #[should_panic(expected = "(shapes [2, 2] and [1, 4])")]
+    fn test_assert_not_approx_ndarray_shape() {
+        let a: Array2<f64> = array![[1.0, 2.0], [3.0, 4.0]];
+        a.assert_approx(array![[1.0, 2.0, 3.0, 4.0]]);
+    }
+}
@@ -0,0 +1,74 @@
+//! Implementations for [`num_complex::Complex`].
+use crate::{ApproxEq, ApproxTolerance};
+use core::{borrow::Borrow, fmt::Debug};
+use num_complex::Complex;
+
+/// Compare complex numbers by their modulus only.
+#[cfg(feature = "std")]
+pub trait ApproxMagnitude: ApproxEq {
+    /// Evaluate if the moduli of the values are approximately equal, ignoring their phase.
+    fn approx_magnitude<T: Borrow<Self>>(&self, other: T) -> bool;
+}
+
+/// The real and imaginary parts are compared separately, and both must be approximately equal.
+impl<A> ApproxEq for Complex<A>
+where
+    A: ApproxEq + Debug + Clone,
+{
+    fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+        let other = other.borrow();
+        self.re.approx(&other.re) && self.im.approx(&other.im)
+    }
+}
+
+impl<A> ApproxTolerance for Complex<A>
+where
+    A: ApproxTolerance + Debug + Clone,
+{
+    type Tolerance = A::Tolerance;
+    const TOLERANCE: Self::Tolerance = A::TOLERANCE;
+    fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: Self::Tolerance) -> bool {
+        let other = other.borrow();
+        self.re.approx_with_tolerance(&other.re, tol)
+            && self.im.approx_with_tolerance(&other.im, tol)
+    }
+}
+
+#[cfg(feature = "std")]
+macro_rules! impl_magnitude {
+    ($($float:ty),*) => {$(
+        impl ApproxMagnitude for Complex<$float> {
+            fn approx_magnitude<T: Borrow<Self>>(&self, other: T) -> bool {
+                self.norm().approx(other.borrow().norm())
+            }
+        }
+    )*};
+}
+
+#[cfg(feature = "std")]
+impl_magnitude!(f32, f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::ops::Not;
+
+    #[test]
+    fn test_approx_complex() {
+        let a = Complex::new(1.0f64, 2.0);
+        assert!(a.approx(Complex::new(1.0000001, 2.0)));
+        assert!(a.approx(Complex::new(1.0, 2.00001)).not());
+        assert!(a.approx(Complex::new(1.00001, 2.0)).not());
+        assert!(a.approx_with_tolerance(Complex::new(1.0, 2.00001), 1e-4));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_approx_magnitude() {
+        let a = Complex::new(3.0f64, 4.0);
+        assert!(a.approx_magnitude(Complex::new(0.0, 5.0)));
+        assert!(a.approx_magnitude(Complex::new(-5.0, 0.0000001)));
+        assert!(a.approx_magnitude(Complex::new(0.0, 5.1)).not());
+        assert!(Complex::new(1.0f32, 0.0).approx_magnitude(Complex::new(0.0, -1.0)));
+    }
+}
@@ -0,0 +1,65 @@
+//! Implementations for [`ordered_float::OrderedFloat`] and [`ordered_float::NotNan`].
+//!
+//! Both forward to the wrapped float, so the values compare like [`f32`] and [`f64`] do.
+//! A [`NotNan`] can never hold NaN, so the NaN caveats of the float comparisons do not apply to it.
+use crate::{ApproxEq, ApproxTolerance};
+use core::borrow::Borrow;
+use ordered_float::{NotNan, OrderedFloat};
+
+macro_rules! impl_ordered_float {
+    ($($wrapper:ident<$float:ty>),* $(,)?) => {$(
+        impl ApproxEq for $wrapper<$float> {
+            fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+                (**self).approx(**other.borrow())
+            }
+        }
+
+        impl ApproxTolerance for $wrapper<$float> {
+            type Tolerance = $float;
+            const TOLERANCE: $float = <$float>::TOLERANCE;
+            fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: $float) -> bool {
+                (**self).approx_with_tolerance(**other.borrow(), tol)
+            }
+        }
+    )*};
+}
+
+impl_ordered_float! {
+    OrderedFloat<f32>,
+    OrderedFloat<f64>,
+    NotNan<f32>,
+    NotNan<f64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::ops::Not;
+
+    #[test]
+    fn test_approx_ordered_float() {
+        let mut a = [OrderedFloat(3.0f64), OrderedFloat(1.0), OrderedFloat(2.0)];
+        a.sort();
+        assert!(a.approx([
+            OrderedFloat(1.0000001),
+            OrderedFloat(2.0),
+            OrderedFloat(3.0)
+        ]));
+        assert!(a
+            .approx([OrderedFloat(3.0), OrderedFloat(2.0), OrderedFloat(1.0)])
+            .not());
+        assert!(OrderedFloat(1.0f32).approx_with_tolerance(OrderedFloat(1.1), 0.2));
+        assert!(OrderedFloat(f64::NAN).approx(OrderedFloat(f64::NAN)).not());
+    }
+
+    #[test]
+    fn test_approx_not_nan() {
+        let mut a = [NotNan::new(2.0f32).unwrap(), NotNan::new(1.0).unwrap()];
+        a.sort();
+        assert!(a.approx([NotNan::new(1.0001).unwrap(), NotNan::new(2.0).unwrap()]));
+        assert!(a[0].approx(NotNan::new(1.01).unwrap()).not());
+        assert!(NotNan::new(1.0f64)
+            .unwrap()
+            .approx_with_tolerance(NotNan::new(1.5).unwrap(), 0.5));
+    }
+}
@@ -0,0 +1,57 @@
+//! Implementations for [`rust_decimal::Decimal`].
+//!
+//! Decimals compare by value, so `1.00` and `1.000` are equal whatever their scales.
+use crate::{ApproxEq, ApproxTolerance};
+use core::borrow::Borrow;
+use rust_decimal::Decimal;
+
+impl ApproxEq for Decimal {
+    fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+        self.approx_with_tolerance(other, Self::TOLERANCE)
+    }
+}
+
+/// Decimals are approximately equal within `1e-9` by default, and `tol` is the largest allowed absolute difference.
+///
+/// A difference that overflows the decimal range is larger than any tolerance.
+impl ApproxTolerance for Decimal {
+    type Tolerance = Self;
+    const TOLERANCE: Self = Self::from_parts(1, 0, 0, false, 9);
+    fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: Self) -> bool {
+        self.checked_sub(*other.borrow())
+            .is_some_and(|diff| diff.abs() <= tol)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::ops::Not;
+
+    #[test]
+    fn test_approx_decimal_scales() {
+        let a = Decimal::new(100, 2);
+        let b = Decimal::new(1000, 3);
+        assert_eq!((a.scale(), b.scale()), (2, 3));
+        assert!(a.approx(b));
+        assert!(Decimal::new(1, 0).approx(Decimal::new(10000000001, 10)));
+        assert!(Decimal::new(1, 0).approx(Decimal::new(1000000002, 9)).not());
+        assert!(Decimal::new(-250, 2).approx(Decimal::new(-25, 1)));
+    }
+
+    #[test]
+    fn test_approx_decimal_tolerance() {
+        let price = Decimal::new(1999, 2);
+        let tol = Decimal::new(1, 2);
+        assert!(price.approx_with_tolerance(Decimal::new(2000, 2), tol));
+        assert!(price
+            .approx_with_tolerance(Decimal::new(200001, 4), tol)
+            .not());
+        assert!(price.approx_with_tolerance(Decimal::new(19985, 3), tol));
+        assert_eq!(Decimal::default_tolerance(), Decimal::new(1, 9));
+        assert!(Decimal::MAX.approx(Decimal::MAX));
+        assert!(Decimal::MAX
+            .approx_with_tolerance(Decimal::MIN, Decimal::MAX)
+            .not());
+    }
+}
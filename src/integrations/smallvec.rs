@@ -0,0 +1,87 @@
+//! Implementations for [`smallvec::SmallVec`].
+use crate::{ApproxEq, ApproxTolerance};
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use core::{borrow::Borrow, fmt::Debug};
+use smallvec::{Array, SmallVec};
+
+/// Compare the contents like slices, whether they are stored inline or spilled onto the heap.
+impl<A> ApproxEq for SmallVec<A>
+where
+    A: Array,
+    A::Item: ApproxEq + Debug + Clone,
+{
+    fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+        self[..].approx(&other.borrow()[..])
+    }
+    fn assert_approx<T: Borrow<Self> + Debug + Clone>(&self, other: T) {
+        self[..].assert_approx(&other.borrow()[..]);
+    }
+    fn first_mismatch_index<T: Borrow<Self>>(&self, other: T) -> Option<usize> {
+        self[..].first_mismatch_index(&other.borrow()[..])
+    }
+    #[cfg(feature = "alloc")]
+    fn first_mismatch<T: Borrow<Self>>(&self, other: T) -> Option<String> {
+        self[..].first_mismatch(&other.borrow()[..])
+    }
+    fn approx_distance<T: Borrow<Self>>(&self, other: T) -> f64 {
+        self[..].approx_distance(&other.borrow()[..])
+    }
+}
+
+impl<A> ApproxTolerance for SmallVec<A>
+where
+    A: Array,
+    A::Item: ApproxTolerance + Debug + Clone,
+{
+    type Tolerance = <A::Item as ApproxTolerance>::Tolerance;
+    const TOLERANCE: Self::Tolerance = A::Item::TOLERANCE;
+    fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: Self::Tolerance) -> bool {
+        self[..].approx_with_tolerance(&other.borrow()[..], tol)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::ops::Not;
+    use smallvec::smallvec;
+
+    #[test]
+    fn test_approx_small_vec() {
+        let inline: SmallVec<[f32; 4]> = smallvec![1.0, 2.0, 3.0];
+        let mut spilled = SmallVec::<[f32; 4]>::with_capacity(8);
+        spilled.extend([1.0001, 2.0, 3.0]);
+        assert!(inline.spilled().not());
+        assert!(spilled.spilled());
+        assert!(inline.approx(&spilled));
+        assert!(spilled.approx(&inline));
+        spilled.push(4.0);
+        assert!(inline.approx(&spilled).not());
+        assert!(inline.approx_with_tolerance(smallvec![1.1, 2.0, 3.0], 0.2));
+    }
+
+    #[test]
+    fn test_small_vec_mismatch() {
+        let a: SmallVec<[f64; 2]> = smallvec![1.0, 2.0, 3.0];
+        let b: SmallVec<[f64; 2]> = smallvec![1.0, 2.5, 3.0];
+        assert_eq!(a.first_mismatch_index(&a), None);
+        assert_eq!(a.first_mismatch_index(&b), Some(1));
+        assert_eq!(a.approx_distance(&b), 0.5);
+        #[cfg(feature = "alloc")]
+        {
+            let nested: SmallVec<[SmallVec<[f64; 2]>; 2]> = smallvec![a.clone(), a.clone()];
+            assert_eq!(
+                nested.first_mismatch(smallvec![a, b]).as_deref(),
+                Some("[1][1]")
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "slices differ at index 1: 2.0 != 2.5 (1 of 3 elements differ)")]
+    fn test_assert_not_approx_small_vec() {
+        let a: SmallVec<[f64; 2]> = smallvec![1.0, 2.0, 3.0];
+        a.assert_approx(smallvec![1.0, 2.5, 3.0]);
+    }
+}
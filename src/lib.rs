@@ -2,124 +2,3618 @@
 //!
 //! ApproxEq is used to provide an approximate equality for the type you want, but your type must implement the approx trait itself.
 //! By default, [`f32`] and [`f64`] have an implementation with tolerances of `1e-3` and `1e-6`, respectively.
+//! Integers are compared exactly unless a tolerance is given with [`ApproxTolerance::approx_with_tolerance`].
+//!
+//! With the opt-in `relative-default` feature, float [`ApproxEq::approx`] uses [`ApproxFloat::approx_combined`]
+//! instead: absolute below [`ApproxFloat::NEAR_ZERO`] and relative above it.
+//! This changes the meaning of `approx` for every type built on floats, including slices and derived structs.
+//!
+//! ## `no_std`
+//!
+//! The traits and the impls for floats, integers, slices, arrays, tuples, [`Option`] and [`Result`] only need `core`,
+//! so the crate builds with `default-features = false`.
+//! The `alloc` feature adds `Vec`, `Box`, `Rc`, `Arc`, `Cow`, `BTreeMap`, `VecDeque`, `BinaryHeap` and `LinkedList`,
+//! and the default `std` feature (which implies `alloc`) adds `HashMap`, `HashSet` and `Instant`.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+#![cfg_attr(feature = "simd", feature(portable_simd))]
 #![deny(rustdoc::broken_intra_doc_links)]
 #![warn(missing_docs)]
 #![warn(clippy::all, clippy::nursery)]
-#![feature(cfg_match)]
-#![feature(portable_simd)]
-use std::{borrow::Borrow, fmt::Debug};
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+use alloc::sync::Arc;
+#[cfg(feature = "alloc")]
+use alloc::{
+    borrow::{Cow, ToOwned},
+    boxed::Box,
+    format,
+    rc::Rc,
+    string::String,
+    vec::Vec,
+};
+use core::{
+    any::Any,
+    borrow::Borrow,
+    cell::{Cell, RefCell},
+    cmp::{Ordering, Reverse},
+    fmt::Debug,
+    marker::PhantomData,
+    num::{
+        NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+        NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Saturating, Wrapping,
+    },
+    ops::{Range, RangeInclusive},
+    sync::atomic,
+    time::Duration,
+};
 
+#[cfg(all(feature = "simd", feature = "relative-default"))]
+use core::simd::Select;
 #[cfg(feature = "simd")]
-use std::simd::{f32x4, f64x4, num::SimdFloat};
+use core::simd::{
+    cmp::{SimdPartialEq, SimdPartialOrd},
+    f32x1, f32x16, f32x2, f32x32, f32x4, f32x64, f32x8, f64x1, f64x16, f64x2, f64x32, f64x4,
+    f64x64, f64x8,
+    num::SimdFloat,
+};
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+#[cfg(feature = "alloc")]
+mod collections;
+mod integrations;
+#[cfg(feature = "parse")]
+mod parse;
+#[cfg(feature = "proptest")]
+mod prop;
+#[cfg(feature = "text")]
+mod text;
+
+#[cfg(feature = "glam")]
+pub use integrations::glam::ApproxRotation;
+#[cfg(all(feature = "num-complex", feature = "std"))]
+pub use integrations::num_complex::ApproxMagnitude;
+#[cfg(feature = "parse")]
+pub use parse::ApproxNumericStr;
+#[cfg(feature = "proptest")]
+pub use prop::prop_assert_approx;
+
+/// Derive [`ApproxEq`] for structs by comparing every field approximately.
+///
+/// Fields marked `#[approx(skip)]` are compared with [`PartialEq`] instead,
+/// and numeric fields marked `#[approx(tolerance = 0.01)]` use [`ApproxTolerance::approx_with_tolerance`].
+#[cfg(feature = "derive")]
+pub use approximately_derive::ApproxEq;
 
 /// Evaluate if the values.
 #[allow(dead_code)]
 pub trait ApproxEq: Debug {
     /// Evaluate if the values are approximately equal.
     fn approx<T: Borrow<Self>>(&self, other: T) -> bool;
+    /// Evaluate if the values are not approximately equal.
+    fn approx_ne<T: Borrow<Self>>(&self, other: T) -> bool {
+        !self.approx(other)
+    }
+    /// Evaluate if the values are approximately equal according to `cmp`, ignoring the impl of the type.
+    ///
+    /// An escape hatch for a one-off comparison the impl does not offer, such as comparing only some fields
+    /// or using a domain-specific metric, without writing a wrapper type.
+    ///
+    /// ```
+    /// use approximately::ApproxEq;
+    ///
+    /// let (a, b) = (1.0f64, 1.04);
+    /// assert!(!a.approx(b));
+    /// assert!(a.approx_by(b, |a, b| (a - b).abs() / a.abs() < 0.05));
+    /// ```
+    fn approx_by<T: Borrow<Self>, F: Fn(&Self, &Self) -> bool>(&self, other: T, cmp: F) -> bool {
+        cmp(self, other.borrow())
+    }
+    /// Evaluate if the values are approximately equal, without a generic argument.
+    ///
+    /// The generic methods keep [`ApproxEq`] from being object-safe even so,
+    /// so use [`DynApproxEq`] for `dyn` values of possibly different types.
+    fn approx_dyn(&self, other: &Self) -> bool {
+        self.approx(other)
+    }
     /// Panic when the values are not equal.
     fn assert_approx<T: Borrow<Self> + Debug + Clone>(&self, other: T) {
         assert!(self.approx(other.clone()), "{self:?} != {other:?}");
     }
+    /// Panic when the values are not equal, but only with `debug_assertions` like [`debug_assert!`].
+    ///
+    /// The argument is still evaluated in release builds, only the comparison is skipped.
+    fn debug_assert_approx<T: Borrow<Self> + Debug + Clone>(&self, other: T) {
+        if cfg!(debug_assertions) {
+            self.assert_approx(other);
+        }
+    }
+    /// Panic when the values are not equal, with a backtrace of the comparison in the panic message.
+    ///
+    /// Needs the `backtrace` feature, which implies `std`. The backtrace is captured even without `RUST_BACKTRACE`,
+    /// but only when the assertion fails, so passing comparisons cost the same as [`ApproxEq::assert_approx`].
+    /// Capturing and resolving the symbols of a backtrace is slow, taking up to milliseconds per failure.
+    #[cfg(feature = "backtrace")]
+    fn assert_approx_traced<T: Borrow<Self> + Debug + Clone>(&self, other: T) {
+        if !self.approx(other.clone()) {
+            let backtrace = std::backtrace::Backtrace::force_capture();
+            panic!("{self:?} != {other:?}\nbacktrace:\n{backtrace}");
+        }
+    }
+    /// For collections, the index of the first element that is not approximately equal, or where the shorter one ends.
+    ///
+    /// Returns [`None`] if the values are approximately equal or have no elements.
+    /// The slice [`ApproxEq::assert_approx`] uses it to locate a divergence in nested collections as `(row, col)`.
+    fn first_mismatch_index<T: Borrow<Self>>(&self, _other: T) -> Option<usize> {
+        None
+    }
+    /// The path to the first divergence in nested values, such as `[2][0].1` for the second field
+    /// of the first tuple in the third vector of a `Vec<Vec<(f64, f64)>>`.
+    ///
+    /// Slices, arrays and vectors add `[index]`, tuples add `.index`, and references and [`Some`] pass through.
+    /// Returns [`None`] if the values are approximately equal, and an empty path if the values themselves differ.
+    #[cfg(feature = "alloc")]
+    fn first_mismatch<T: Borrow<Self>>(&self, other: T) -> Option<String> {
+        self.approx_ne(other).then(String::new)
+    }
+    /// Measure how far apart the values are as one [`f64`], comparable across types, such as to plot convergence.
+    ///
+    /// By default this is `0.0` if the values are approximately equal and infinite otherwise.
+    /// Numbers return their absolute difference, and slices, arrays and vectors the largest distance of their elements.
+    fn approx_distance<T: Borrow<Self>>(&self, other: T) -> f64 {
+        if self.approx(other) {
+            0.0
+        } else {
+            f64::INFINITY
+        }
+    }
 }
 
-impl ApproxEq for f32 {
-    fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
-        (self - other.borrow()).abs() <= 1e-3
+/// Assert that two expressions are approximately equal, see [`ApproxEq::approx`].
+///
+/// On failure the panic message contains both expressions and their debug values.
+/// An optional trailing format message is appended like [`assert!`].
+#[macro_export]
+macro_rules! assert_approx {
+    ($left:expr, $right:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if !$crate::ApproxEq::approx(left, right) {
+                    panic!(
+                        "assertion `{} approx {}` failed\n  left: {:?}\n right: {:?}",
+                        stringify!($left),
+                        stringify!($right),
+                        left,
+                        right
+                    );
+                }
+            }
+        }
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if !$crate::ApproxEq::approx(left, right) {
+                    panic!(
+                        "assertion `{} approx {}` failed: {}\n  left: {:?}\n right: {:?}",
+                        stringify!($left),
+                        stringify!($right),
+                        format_args!($($arg)+),
+                        left,
+                        right
+                    );
+                }
+            }
+        }
+    };
+}
+
+/// Assert that two expressions are not approximately equal, see [`ApproxEq::approx_ne`].
+///
+/// On failure the panic message contains both expressions and their debug values.
+/// An optional trailing format message is appended like [`assert!`].
+#[macro_export]
+macro_rules! assert_approx_ne {
+    ($left:expr, $right:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if !$crate::ApproxEq::approx_ne(left, right) {
+                    panic!(
+                        "assertion `{} not approx {}` failed\n  left: {:?}\n right: {:?}",
+                        stringify!($left),
+                        stringify!($right),
+                        left,
+                        right
+                    );
+                }
+            }
+        }
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if !$crate::ApproxEq::approx_ne(left, right) {
+                    panic!(
+                        "assertion `{} not approx {}` failed: {}\n  left: {:?}\n right: {:?}",
+                        stringify!($left),
+                        stringify!($right),
+                        format_args!($($arg)+),
+                        left,
+                        right
+                    );
+                }
+            }
+        }
+    };
+}
+
+/// Assert that every row `(left, right, tol)` of a table is approximately equal within its own tolerance,
+/// see [`ApproxTolerance::approx_with_tolerance`].
+///
+/// On failure the panic message contains the index of the first failing row, its expressions and their debug values.
+///
+/// ```
+/// use approximately::assert_approx_table;
+///
+/// assert_approx_table![
+///     (0.5f64.sqrt(), 0.7071, 1e-4),
+///     (1.0f64.exp(), 2.71828, 1e-5),
+///     (10u32.pow(3), 1001, 1),
+/// ];
+/// ```
+#[macro_export]
+macro_rules! assert_approx_table {
+    ($(($left:expr, $right:expr, $tol:expr)),+ $(,)?) => {{
+        $crate::assert_approx_table!(@row 0usize; $(($left, $right, $tol)),+);
+    }};
+    (@row $row:expr; ($left:expr, $right:expr, $tol:expr) $(, $rest:tt)*) => {
+        match (&$left, &$right, $tol) {
+            (left, right, tol) => {
+                if !$crate::ApproxTolerance::approx_with_tolerance(left, right, tol) {
+                    panic!(
+                        "assertion `{} approx {}` failed in row {}\n  left: {:?}\n right: {:?}\n   tol: {:?}",
+                        stringify!($left),
+                        stringify!($right),
+                        $row,
+                        left,
+                        right,
+                        tol
+                    );
+                }
+            }
+        }
+        $crate::assert_approx_table!(@row $row + 1; $($rest),*);
+    };
+    (@row $row:expr;) => {};
+}
+
+/// Provide the tolerance used to evaluate approximate equality.
+///
+/// The numeric impls of [`ApproxEq`] read their tolerance from [`ApproxTolerance::TOLERANCE`],
+/// so a wrapper type only has to pick its own constant.
+pub trait ApproxTolerance: ApproxEq {
+    /// The type the tolerance is expressed in.
+    type Tolerance: Copy;
+    /// The largest absolute difference still considered approximately equal.
+    const TOLERANCE: Self::Tolerance;
+    /// Evaluate if the values are approximately equal within `tol`.
+    fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: Self::Tolerance) -> bool;
+    /// The tolerance [`ApproxEq::approx`] compares with, for reporting code such as test frameworks.
+    ///
+    /// This is [`ApproxTolerance::TOLERANCE`], callable without naming the trait constant, like `f64::default_tolerance()`.
+    fn default_tolerance() -> Self::Tolerance {
+        Self::TOLERANCE
     }
-    fn assert_approx<T: Borrow<Self> + Debug + Clone>(&self, other: T) {
-        assert!(self.approx(other.clone()), "{self:.3?} != {other:.3?}");
+}
+
+/// Floating point comparisons beyond the absolute tolerance of [`ApproxEq::approx`].
+///
+/// [`ApproxEq::approx`] follows IEEE semantics: NaN is never approximately equal to anything, including itself.
+/// An infinity is only approximately equal to the infinity of the same sign.
+/// Use [`ApproxFloat::approx_with_nan_eq`] when a computation may legitimately produce NaN.
+pub trait ApproxFloat: ApproxTolerance<Tolerance = Self> + Copy {
+    /// The default relative tolerance used by [`ApproxFloat::approx_relative`].
+    const RELATIVE_TOLERANCE: Self;
+    /// Evaluate if the values are approximately equal relative to their magnitude.
+    ///
+    /// The values are equal when `|a - b| <= max(|a|, |b|) * RELATIVE_TOLERANCE`, so two zeros are always equal,
+    /// and an infinity is only equal to the same infinity.
+    /// Prefer this over [`ApproxEq::approx`] when the magnitude of the values is large or unknown:
+    /// a fixed absolute tolerance is too strict for `1e9` and too loose for `1e-9`.
+    /// Near zero the scaled tolerance vanishes, so keep using [`ApproxEq::approx`] there.
+    fn approx_relative<T: Borrow<Self>>(&self, other: T) -> bool;
+    /// Evaluate if the values are approximately equal relative to the larger magnitude, in either argument order.
+    ///
+    /// `a.approx_symmetric(b) == b.approx_symmetric(a)` always holds, as it does for the absolute [`ApproxEq::approx`].
+    /// This is [`ApproxFloat::approx_relative`], named for the guarantee,
+    /// unlike [`ApproxFloat::approx_within_percent`] and [`ApproxFloat::approx_relative_to`] which scale by a reference value.
+    fn approx_symmetric<T: Borrow<Self>>(&self, other: T) -> bool {
+        self.approx_relative(other)
+    }
+    /// Evaluate if the values are approximately equal relative to a known `reference` value, such as an exact answer.
+    ///
+    /// The values are equal when `|a - b| <= |reference| * rel_tol`, independently of the magnitude of `a` and `b`.
+    fn approx_relative_to<T: Borrow<Self>>(&self, other: T, reference: Self, rel_tol: Self)
+        -> bool;
+    /// The magnitude below which [`ApproxFloat::approx_combined`] uses the absolute tolerance.
+    ///
+    /// It is `TOLERANCE / RELATIVE_TOLERANCE`, where both tolerances allow the same error,
+    /// `1e2` for [`f32`] and `1e3` for [`f64`].
+    const NEAR_ZERO: Self;
+    /// Evaluate if the values are approximately equal by combining the absolute and relative tolerances.
+    ///
+    /// If the larger magnitude is below [`ApproxFloat::NEAR_ZERO`] the values are compared with [`ApproxEq::approx`]
+    /// against [`ApproxTolerance::TOLERANCE`] (`1e-3` for [`f32`], `1e-6` for [`f64`]),
+    /// otherwise with [`ApproxFloat::approx_relative`] against [`ApproxFloat::RELATIVE_TOLERANCE`] (`1e-5` and `1e-9`).
+    /// So values computed near zero, such as `1e-8`, do not fail the vanishing relative tolerance,
+    /// and large values do not fail the fixed absolute one. An infinity is only equal to the same infinity.
+    fn approx_combined<T: Borrow<Self>>(&self, other: T) -> bool;
+    /// Evaluate if the values are approximately equal within the tolerance of the marker `E`, such as [`Tight`] or [`Eps`].
+    ///
+    /// The tolerance is a constant of the type, so generic code monomorphizes it without storing or branching on it.
+    fn approx_as<E: Epsilon<Self>, T: Borrow<Self>>(&self, other: T) -> bool {
+        self.approx_with_tolerance(other, E::TOLERANCE)
     }
+    /// Panic with `msg` and the difference when the values are not approximately equal within `tol`.
+    fn assert_approx_with<T: Borrow<Self> + Debug + Clone>(&self, other: T, tol: Self, msg: &str);
+    /// Panic with the difference when the values differ by `min_diff` or less, such as to check that an update changed a value.
+    ///
+    /// The difference must be strictly larger than `min_diff`, so a NaN difference panics too.
+    fn assert_approx_differs<T: Borrow<Self> + Debug + Clone>(&self, other: T, min_diff: Self);
+    /// Evaluate if the values are approximately equal within `factor` times [`ApproxFloat::current_tolerance`].
+    ///
+    /// Rounding errors grow with the number of operations, so a reduction over `n` terms,
+    /// such as a sum or a dot product, is typically compared with a factor of `n`.
+    fn approx_scaled<T: Borrow<Self>>(&self, other: T, factor: Self) -> bool;
+    /// Evaluate if the value is within `percent` percent of `other`, such as `100.0.approx_within_percent(101.0, 2.0)`.
+    ///
+    /// The values are equal when `|a - b| <= |b| * percent / 100`, so `other` is the reference value.
+    /// If `other` is zero there is nothing to take a percentage of, and the values are compared with [`ApproxEq::approx`].
+    fn approx_within_percent<T: Borrow<Self>>(&self, other: T, percent: Self) -> bool;
+    /// Evaluate if the angles in radians are approximately equal, so `0.0` and `TAU` are the same angle.
+    ///
+    /// The difference is wrapped into `[-π, π]` before it is compared against [`ApproxTolerance::TOLERANCE`].
+    fn approx_angle<T: Borrow<Self>>(&self, other: T) -> bool;
+    /// Evaluate if the angles in degrees are approximately equal, so `0.0` and `360.0` are the same angle.
+    ///
+    /// The angles are converted to radians and compared with [`ApproxFloat::approx_angle`].
+    fn approx_angle_deg<T: Borrow<Self>>(&self, other: T) -> bool;
+    /// Evaluate if the values are at most `max_ulps` representable floats apart.
+    ///
+    /// The distance is measured in units in the last place, so the allowed error scales with the magnitude of the values.
+    /// `0.0` and `-0.0` are 0 ULPs apart, values on opposite sides of zero are counted through zero,
+    /// and NaN is never equal to anything.
+    fn approx_ulps<T: Borrow<Self>>(&self, other: T, max_ulps: u32) -> bool;
+    /// Evaluate if the values are approximately equal, treating two NaNs as equal.
+    ///
+    /// Every other value is compared with [`ApproxEq::approx`].
+    fn approx_with_nan_eq<T: Borrow<Self>>(&self, other: T) -> bool;
+    /// Evaluate if the values are approximately equal, telling `0.0` and `-0.0` apart.
+    ///
+    /// In complex analysis the sign of a zero picks the side of a branch cut:
+    /// `atan2(0.0, -1.0)` is `π` but `atan2(-0.0, -1.0)` is `-π`, so results differing only in that sign are far apart.
+    /// Two zeros are equal only if their signs match, and every other value is compared with [`ApproxEq::approx`].
+    fn approx_signed_zero<T: Borrow<Self>>(&self, other: T) -> bool;
+    /// Evaluate if the values are approximately equal according to `cfg`.
+    ///
+    /// The values are equal if any of the checks set in `cfg` holds, see [`ApproxConfig`].
+    fn approx_cfg<T: Borrow<Self>>(&self, other: T, cfg: &ApproxConfig<Self>) -> bool;
+    /// The absolute tolerance [`ApproxEq::approx`] uses on this thread right now.
+    ///
+    /// This is the tolerance set by [`with_tolerance_override`] inside its closure, and [`ApproxTolerance::TOLERANCE`] otherwise.
+    fn current_tolerance() -> Self;
 }
 
-impl ApproxEq for f64 {
-    fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
-        (self - other.borrow()).abs() <= 1e-6
+/// Combine several float comparisons into one configuration for [`ApproxFloat::approx_cfg`].
+///
+/// The values are approximately equal if they are within [`abs_tol`](ApproxConfig::abs_tol)
+/// OR within [`rel_tol`](ApproxConfig::rel_tol) relative to the larger magnitude
+/// OR at most [`max_ulps`](ApproxConfig::max_ulps) apart, skipping the checks that are [`None`].
+/// With no check set only identical values are equal.
+/// [`ApproxConfig::default`] matches [`ApproxEq::approx`].
+///
+/// With the `serde` feature the configuration can be loaded from a data file,
+/// using the field names `abs_tol`, `rel_tol`, `max_ulps` and `nan_equal`.
+/// Absent fields take their value from [`ApproxConfig::default`],
+/// so `{ "rel_tol": 1e-6 }` still checks the default absolute tolerance while `{ "abs_tol": null }` disables it.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(
+        default,
+        bound(deserialize = "F: ApproxFloat + serde::Deserialize<'de>")
+    )
+)]
+pub struct ApproxConfig<F> {
+    /// The absolute tolerance.
+    pub abs_tol: Option<F>,
+    /// The relative tolerance, scaled by the larger magnitude of the values.
+    pub rel_tol: Option<F>,
+    /// The largest distance in units in the last place.
+    pub max_ulps: Option<u32>,
+    /// Whether two NaNs are approximately equal.
+    pub nan_equal: bool,
+}
+
+impl<F: ApproxFloat> Default for ApproxConfig<F> {
+    fn default() -> Self {
+        Self {
+            abs_tol: Some(F::TOLERANCE),
+            rel_tol: None,
+            max_ulps: None,
+            nan_equal: false,
+        }
     }
-    fn assert_approx<T: Borrow<Self> + Debug + Clone>(&self, other: T) {
-        assert!(self.approx(other.clone()), "{self:.6?} != {other:.6?}");
+}
+
+impl<F: Copy> ApproxConfig<F> {
+    /// Set the absolute tolerance.
+    pub const fn abs_tol(mut self, tol: Option<F>) -> Self {
+        self.abs_tol = tol;
+        self
+    }
+    /// Set the relative tolerance.
+    pub const fn rel_tol(mut self, tol: Option<F>) -> Self {
+        self.rel_tol = tol;
+        self
+    }
+    /// Set the largest distance in units in the last place.
+    pub const fn max_ulps(mut self, max_ulps: Option<u32>) -> Self {
+        self.max_ulps = max_ulps;
+        self
+    }
+    /// Set whether two NaNs are approximately equal.
+    pub const fn nan_equal(mut self, nan_equal: bool) -> Self {
+        self.nan_equal = nan_equal;
+        self
     }
 }
 
-impl<A> ApproxEq for [A]
+/// A value carrying the tolerance it is compared with, such as a row of a table-driven test,
+/// created by [`Tolerance::new`] or [`WithTolerance::tol`].
+///
+/// Two values are approximately equal if they are within the `eps` of either one,
+/// using [`ApproxTolerance::approx_with_tolerance`].
+/// A plain value converts to a [`Tolerance`] with a zero `eps`,
+/// so [`Tolerance::approx`] also compares against plain values, with `eps` alone.
+#[derive(Debug, Clone, Copy)]
+pub struct Tolerance<T> {
+    /// The value to compare.
+    pub value: T,
+    /// The tolerance to compare the value with.
+    pub eps: T,
+}
+
+impl<T> Tolerance<T> {
+    /// Attach the tolerance `eps` to `value`.
+    pub const fn new(value: T, eps: T) -> Self {
+        Self { value, eps }
+    }
+}
+
+/// A plain value carries no tolerance of its own, so it converts with a zero `eps`.
+impl<T: Default> From<T> for Tolerance<T> {
+    fn from(value: T) -> Self {
+        Self::new(value, T::default())
+    }
+}
+
+impl<T: ApproxTolerance<Tolerance = T> + Copy + Default> Tolerance<T> {
+    /// Evaluate if the values are approximately equal, where `other` is a [`Tolerance`] or a plain value.
+    ///
+    /// This is [`ApproxEq::approx`] after converting `other`, so against a plain value only `eps` applies.
+    pub fn approx<U: Into<Self>>(&self, other: U) -> bool {
+        ApproxEq::approx(self, other.into())
+    }
+    /// Panic with the tolerance when the values are not approximately equal, see [`Tolerance::approx`].
+    #[track_caller]
+    pub fn assert_approx<U: Into<Self>>(&self, other: U)
+    where
+        T: Debug,
+    {
+        let other = other.into();
+        assert!(
+            self.approx(other),
+            "{:?} != {:?} (tolerance {:?})",
+            self.value,
+            other.value,
+            self.eps
+        );
+    }
+}
+
+impl<T> ApproxEq for Tolerance<T>
 where
-    A: ApproxEq + Debug + Clone,
+    T: ApproxTolerance<Tolerance = T> + Copy,
 {
-    fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
-        if self.len() != other.borrow().len() {
-            return false;
-        }
-        self.iter()
-            .zip(other.borrow().iter())
-            .all(|(a, b)| a.approx(b))
+    fn approx<U: Borrow<Self>>(&self, other: U) -> bool {
+        let other = other.borrow();
+        self.value.approx_with_tolerance(other.value, self.eps)
+            || self.value.approx_with_tolerance(other.value, other.eps)
     }
-    fn assert_approx<T: Borrow<Self> + Debug + Clone>(&self, other: T) {
-        assert!(self.approx(other.clone()), "{self:?} != {other:?}");
+}
+
+/// An object-safe form of [`ApproxEq`], implemented for every `'static` type that implements it,
+/// such as to keep expected values of different types in one `Vec<Box<dyn DynApproxEq>>`.
+///
+/// Values of different types are never approximately equal, and only the default tolerance is available.
+///
+/// ```
+/// use approximately::DynApproxEq;
+///
+/// let expected: Vec<Box<dyn DynApproxEq>> = vec![Box::new(1.0f64), Box::new([1u8, 2]), Box::new(Some(2u8))];
+/// let actual: Vec<Box<dyn DynApproxEq>> = vec![Box::new(1.0000001f64), Box::new([1u8, 2]), Box::new(2u8)];
+/// let matches: Vec<bool> = expected.iter().zip(&actual).map(|(a, b)| a.dyn_approx(&**b)).collect();
+/// assert_eq!(matches, [true, true, false]);
+/// ```
+pub trait DynApproxEq: Debug {
+    /// Evaluate if `other` has the same type and is approximately equal.
+    fn dyn_approx(&self, other: &dyn DynApproxEq) -> bool;
+    /// Access the value for downcasting.
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<A: ApproxEq + Any> DynApproxEq for A {
+    fn dyn_approx(&self, other: &dyn DynApproxEq) -> bool {
+        other
+            .as_any()
+            .downcast_ref::<A>()
+            .is_some_and(|other| self.approx_dyn(other))
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
     }
 }
 
-impl<A> ApproxEq for Option<A>
+/// Compare floats of different precisions, such as an [`f32`] result against an [`f64`] reference.
+pub trait ApproxCross: Copy {
+    /// The float type of the other precision.
+    type Other: Copy;
+    /// Evaluate if the values are within `tol` of each other after promoting both to [`f64`].
+    ///
+    /// The [`f32`] is widened exactly, never the [`f64`] rounded, so `tol` applies to the full-precision reference.
+    /// The [`f32`] still carries its own rounding error, so `tol` should be at least its precision, about `1e-7` relative.
+    fn approx_cross(&self, other: Self::Other, tol: f64) -> bool;
+}
+
+impl ApproxCross for f32 {
+    type Other = f64;
+    fn approx_cross(&self, other: f64, tol: f64) -> bool {
+        f64::from(*self).approx_with_tolerance(other, tol)
+    }
+}
+
+impl ApproxCross for f64 {
+    type Other = f32;
+    fn approx_cross(&self, other: f32, tol: f64) -> bool {
+        self.approx_with_tolerance(Self::from(other), tol)
+    }
+}
+
+/// Attach a tolerance to a value, see [`WithTolerance::tol`].
+pub trait WithTolerance: ApproxTolerance<Tolerance = Self> + Copy {
+    /// Keep the tolerance `eps` next to the value it is compared with, as a [`Tolerance`].
+    ///
+    /// ```
+    /// use approximately::WithTolerance;
+    ///
+    /// assert!(1.0_f64.tol(0.01).approx(1.005));
+    /// assert!(!1.0_f64.tol(0.001).approx(1.005));
+    /// 100u32.tol(2).assert_approx(102);
+    /// ```
+    fn tol(self, eps: Self) -> Tolerance<Self> {
+        Tolerance::new(self, eps)
+    }
+}
+
+impl<T: ApproxTolerance<Tolerance = T> + Copy> WithTolerance for T {}
+
+/// A tolerance chosen at compile time by a marker type, see [`ApproxFloat::approx_as`].
+pub trait Epsilon<F> {
+    /// The largest absolute difference still considered approximately equal.
+    const TOLERANCE: F;
+}
+
+/// A marker for the tolerance `10^EXP`, such as `Eps<-6>` for `1e-6`.
+#[derive(Debug, Clone, Copy)]
+pub struct Eps<const EXP: i32>;
+
+/// Compute `10^exp` in a constant context, exactly for `|exp| <= 22`.
+const fn pow10(exp: i32) -> f64 {
+    let mut value = 1.0;
+    let mut i = 0;
+    while i < exp.unsigned_abs() {
+        value *= 10.0;
+        i += 1;
+    }
+    if exp < 0 {
+        1.0 / value
+    } else {
+        value
+    }
+}
+
+impl<const EXP: i32> Epsilon<f32> for Eps<EXP> {
+    const TOLERANCE: f32 = pow10(EXP) as f32;
+}
+
+impl<const EXP: i32> Epsilon<f64> for Eps<EXP> {
+    const TOLERANCE: f64 = pow10(EXP);
+}
+
+/// A marker for a tight tolerance, `1e-6` for [`f32`] and `1e-12` for [`f64`].
+#[derive(Debug, Clone, Copy)]
+pub struct Tight;
+
+impl Epsilon<f32> for Tight {
+    const TOLERANCE: f32 = 1e-6;
+}
+
+impl Epsilon<f64> for Tight {
+    const TOLERANCE: f64 = 1e-12;
+}
+
+/// A marker for a loose tolerance, `1e-2` for [`f32`] and `1e-4` for [`f64`].
+#[derive(Debug, Clone, Copy)]
+pub struct Loose;
+
+impl Epsilon<f32> for Loose {
+    const TOLERANCE: f32 = 1e-2;
+}
+
+impl Epsilon<f64> for Loose {
+    const TOLERANCE: f64 = 1e-4;
+}
+
+/// Measure how far apart two values are.
+pub trait ApproxDiff: ApproxEq {
+    /// The type the difference is expressed in.
+    type Diff;
+    /// Evaluate the difference between the values, useful to report or tune tolerances.
+    fn approx_diff<T: Borrow<Self>>(&self, other: T) -> Self::Diff;
+}
+
+/// Describe why values are not approximately equal, for test harnesses that aggregate failures.
+pub trait ApproxReport: ApproxEq {
+    /// The type of the reported values.
+    type Value;
+    /// Evaluate if the values are approximately equal like [`ApproxEq::approx`], reporting the mismatch otherwise.
+    fn try_approx<T: Borrow<Self>>(&self, other: T) -> Result<(), ReportedMismatch<Self::Value>>;
+}
+
+/// A mismatch reported by [`ApproxReport::try_approx`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportedMismatch<V> {
+    /// The values are further apart than the tolerance.
+    Values {
+        /// The left value, or the left element at `index`.
+        left: V,
+        /// The right value, or the right element at `index`.
+        right: V,
+        /// The absolute difference `|left - right|`.
+        diff: V,
+        /// The signed relative error `(left - right) / |right|`, infinite or NaN if `right` is zero.
+        relative: V,
+        /// The tolerance the difference exceeds.
+        tolerance: V,
+        /// For slices, the index of the elements that differ the most.
+        index: Option<usize>,
+    },
+    /// The slices have different lengths.
+    Lengths {
+        /// The length of the left slice.
+        left: usize,
+        /// The length of the right slice.
+        right: usize,
+    },
+}
+
+/// Order values, treating approximately equal values as equal.
+///
+/// This is not a total order: approximate equality is not transitive,
+/// so `a` and `b`, and `b` and `c`, can be approximately equal while `a` is less than `c`.
+/// Do not use [`ApproxOrd::approx_cmp`] as the comparator of `sort_by` or a `BTreeMap`,
+/// which may panic or misbehave on an inconsistent order. Sort by `total_cmp` and then compare approximately instead.
+pub trait ApproxOrd: ApproxEq {
+    /// Compare the values, yielding [`Ordering::Equal`] when they are approximately equal.
+    fn approx_cmp<T: Borrow<Self>>(&self, other: T) -> Ordering;
+    /// Evaluate if the value is less than `other` and not approximately equal to it.
+    fn approx_lt<T: Borrow<Self>>(&self, other: T) -> bool {
+        self.approx_cmp(other) == Ordering::Less
+    }
+    /// Evaluate if the value is less than or approximately equal to `other`.
+    fn approx_le<T: Borrow<Self>>(&self, other: T) -> bool {
+        self.approx_cmp(other) != Ordering::Greater
+    }
+    /// Evaluate if the value is greater than `other` and not approximately equal to it.
+    fn approx_gt<T: Borrow<Self>>(&self, other: T) -> bool {
+        self.approx_cmp(other) == Ordering::Greater
+    }
+    /// Evaluate if the value is greater than or approximately equal to `other`.
+    fn approx_ge<T: Borrow<Self>>(&self, other: T) -> bool {
+        self.approx_cmp(other) != Ordering::Less
+    }
+}
+
+/// Evaluate if two iterators yield approximately equal items, without collecting them.
+///
+/// Iterators of different lengths are never approximately equal.
+pub fn approx_all<A, I, J>(a: I, b: J) -> bool
 where
-    A: ApproxEq + Debug + Clone,
+    A: ApproxEq,
+    I: IntoIterator<Item = A>,
+    J: IntoIterator<Item = A>,
 {
-    fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
-        match (self, other.borrow()) {
-            (Some(a), Some(b)) => a.approx(b),
-            (None, None) => true,
-            _ => false,
+    let (mut a, mut b) = (a.into_iter(), b.into_iter());
+    loop {
+        match (a.next(), b.next()) {
+            (Some(a), Some(b)) if a.approx(&b) => {}
+            (None, None) => return true,
+            _ => return false,
         }
     }
-    fn assert_approx<T: Borrow<Self> + Debug + Clone>(&self, other: T) {
-        assert!(self.approx(other.clone()), "{self:?} != {other:?}");
+}
+
+/// Compare two sequences pair by pair in constant memory, such as streams too large to collect.
+///
+/// ```
+/// use approximately::ApproxAccumulator;
+///
+/// let mut acc = ApproxAccumulator::new();
+/// for (a, b) in [(1.0f64, 1.0000001), (2.0, 2.5), (3.0, 3.0)] {
+///     acc.push(a, b);
+/// }
+/// assert_eq!(acc.finish(), Err((1, 0.5)));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ApproxAccumulator<A> {
+    len: usize,
+    first_mismatch: Option<usize>,
+    max_distance: f64,
+    marker: PhantomData<fn(A)>,
+}
+
+impl<A> Default for ApproxAccumulator<A> {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
-#[cfg(feature = "simd")]
-impl ApproxEq for f32x4 {
+impl<A> ApproxAccumulator<A> {
+    /// Start with no pairs, which holds trivially.
+    pub const fn new() -> Self {
+        Self {
+            len: 0,
+            first_mismatch: None,
+            max_distance: 0.0,
+            marker: PhantomData,
+        }
+    }
+    /// The number of pairs pushed so far.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+    /// Evaluate if no pairs were pushed yet.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// Evaluate if every pair pushed so far was approximately equal.
+    pub const fn holds(&self) -> bool {
+        self.first_mismatch.is_none()
+    }
+    /// The largest [`ApproxEq::approx_distance`] seen so far, NaN if any distance was NaN.
+    pub const fn max_distance(&self) -> f64 {
+        self.max_distance
+    }
+    /// Finish the comparison, returning the index of the first mismatch and the largest distance if any pair differed.
+    pub const fn finish(self) -> Result<(), (usize, f64)> {
+        match self.first_mismatch {
+            Some(index) => Err((index, self.max_distance)),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<A: ApproxEq> ApproxAccumulator<A> {
+    /// Compare the next pair.
+    pub fn push(&mut self, a: A, b: A) {
+        if self.first_mismatch.is_none() && !a.approx(&b) {
+            self.first_mismatch = Some(self.len);
+        }
+        if let Some(max) = max_diff([self.max_distance, a.approx_distance(&b)].into_iter()) {
+            self.max_distance = max;
+        }
+        self.len += 1;
+    }
+}
+
+/// Count the pairs of elements that are not approximately equal, such as to accept images where most pixels match.
+///
+/// Returns [`usize::MAX`] if the lengths differ, as no pairing of the elements exists.
+///
+/// ```
+/// use approximately::approx_count_mismatches;
+///
+/// assert_eq!(approx_count_mismatches(&[1.0f64, 2.0, 3.0], &[1.0, 2.5, 3.5]), 2);
+/// assert_eq!(approx_count_mismatches(&[1.0f64], &[1.0, 2.0]), usize::MAX);
+/// ```
+pub fn approx_count_mismatches<A: ApproxEq>(a: &[A], b: &[A]) -> usize {
+    if a.len() != b.len() {
+        return usize::MAX;
+    }
+    a.iter().zip(b).filter(|(a, b)| !a.approx(b)).count()
+}
+
+/// Evaluate if every element is at least the previous one minus `tol`, such as a converging series with numerical jitter.
+///
+/// A dip is allowed if the two elements are within `tol` of each other, and NaN is never in order.
+///
+/// ```
+/// use approximately::approx_monotonic_increasing;
+///
+/// assert!(approx_monotonic_increasing(&[0.5f64, 0.75, 0.7499999, 0.875], 1e-6));
+/// assert!(!approx_monotonic_increasing(&[0.5f64, 0.75, 0.7, 0.875], 1e-6));
+/// ```
+pub fn approx_monotonic_increasing<A>(a: &[A], tol: A::Tolerance) -> bool
+where
+    A: ApproxTolerance + PartialOrd,
+{
+    a.windows(2)
+        .all(|pair| pair[1] >= pair[0] || pair[0].approx_with_tolerance(&pair[1], tol))
+}
+
+/// The fraction of element pairs that are approximately equal, from `0.0` to `1.0`.
+///
+/// Elements past the end of the shorter slice have no partner and count as differing,
+/// and two empty slices are entirely similar.
+pub fn approx_similarity_ratio<A: ApproxEq>(a: &[A], b: &[A]) -> f64 {
+    let len = a.len().max(b.len());
+    if len == 0 {
+        return 1.0;
+    }
+    let matches = a.iter().zip(b).filter(|(a, b)| a.approx(b)).count();
+    matches as f64 / len as f64
+}
+
+/// Evaluate if at least `ratio` of the element pairs are approximately equal, see [`approx_similarity_ratio`].
+///
+/// ```
+/// use approximately::approx_at_least;
+///
+/// // Four of five blocks match, so the images are considered identical.
+/// assert!(approx_at_least(&[1u8, 2, 3, 4, 5], &[1, 2, 3, 4, 6], 0.8));
+/// assert!(!approx_at_least(&[1u8, 2, 3, 4, 5], &[1, 2, 3, 5, 6], 0.8));
+/// ```
+pub fn approx_at_least<A: ApproxEq>(a: &[A], b: &[A], ratio: f64) -> bool {
+    approx_similarity_ratio(a, b) >= ratio
+}
+
+/// Find the largest relative error between the elements of two slices and its index, without asserting.
+///
+/// The relative error of two elements is `|a - b| / max(|a|, |b|)`, zero if they are equal and infinite if only one is infinite.
+/// NaN is larger than any error, so it is never hidden. Returns [`None`] if the lengths differ or the slices are empty.
+pub fn max_relative_error<A>(a: &[A], b: &[A]) -> Option<(usize, f64)>
+where
+    A: ApproxFloat + Into<f64>,
+{
+    if a.len() != b.len() {
+        return None;
+    }
+    a.iter()
+        .zip(b.iter())
+        .map(|(&a, &b)| {
+            let (a, b): (f64, f64) = (a.into(), b.into());
+            let scale = a.abs().max(b.abs());
+            if a == b {
+                0.0
+            } else if scale.is_infinite() {
+                f64::INFINITY
+            } else {
+                (a - b).abs() / scale
+            }
+        })
+        .enumerate()
+        .reduce(|max, error| {
+            if max.1.is_nan() || error.1 <= max.1 {
+                max
+            } else {
+                error
+            }
+        })
+}
+
+/// Evaluate if two slices hold approximately equal elements with the same multiplicities, in any order.
+///
+/// Sorted copies of both slices are compared element-wise, with NaN sorted after every other value,
+/// so slices are not approximately equal if either contains NaN, as with [`ApproxEq::approx`].
+/// Values that are approximately equal but sort on different sides of a neighbour can pair up wrongly,
+/// so nearly coincident elements may be reported as different.
+///
+/// ```
+/// use approximately::approx_sorted;
+///
+/// assert!(approx_sorted(&[3.0f32, 1.0, 2.0], &[1.0001, 2.0, 2.9999]));
+/// assert!(!approx_sorted(&[1.0f32, 1.0, 2.0], &[1.0, 2.0, 2.0]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn approx_sorted<A>(a: &[A], b: &[A]) -> bool
+where
+    A: ApproxEq + PartialOrd + Copy,
+{
+    fn sorted<A: PartialOrd + Copy>(values: &[A]) -> Vec<A> {
+        let is_nan = |value: &A| value.partial_cmp(value).is_none();
+        let mut values = values.to_vec();
+        values.sort_by(|a, b| {
+            a.partial_cmp(b)
+                .unwrap_or_else(|| is_nan(a).cmp(&is_nan(b)))
+        });
+        values
+    }
+    a.len() == b.len() && sorted(a).approx(sorted(b))
+}
+
+/// Comparisons of slices beyond element-wise equality.
+pub trait ApproxSlice: ApproxEq {
+    /// Evaluate if the overlapping prefix is approximately equal, ignoring the tail of the longer slice.
+    ///
+    /// Unlike [`ApproxEq::approx`], slices of different lengths can be approximately equal,
+    /// such as signal buffers that differ by a sample or two.
+    fn approx_prefix<T: Borrow<Self>>(&self, other: T) -> bool;
+}
+
+/// Fold element-wise differences into their maximum, letting NaN win so that it is never hidden.
+fn max_diff<D: PartialOrd>(diffs: impl Iterator<Item = D>) -> Option<D> {
+    diffs.reduce(|max, diff| {
+        let max_is_nan = max.partial_cmp(&max).is_none();
+        if max_is_nan || diff <= max {
+            max
+        } else {
+            diff
+        }
+    })
+}
+
+/// Evaluate if two floats differ by at most `tol` times their larger magnitude.
+///
+/// An infinite magnitude would scale the tolerance to infinity and accept any difference,
+/// so an infinity is only within the tolerance of the same infinity.
+macro_rules! within_relative {
+    ($this:expr, $other:expr, $tol:expr) => {{
+        let (this, other, tol) = ($this, $other, $tol);
+        if this.is_infinite() || other.is_infinite() {
+            this == other
+        } else {
+            this == other || (this - other).abs() <= this.abs().max(other.abs()) * tol
+        }
+    }};
+}
+
+macro_rules! impl_float {
+    ($($float:ident, $bits:ty => $tolerance:expr, $relative:expr, $near_zero:expr;)*) => {$(
+        impl ApproxEq for $float {
+            #[cfg(not(feature = "relative-default"))]
+            fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+                self.approx_with_tolerance(other, Self::current_tolerance())
+            }
+            #[cfg(feature = "relative-default")]
+            fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+                self.approx_combined(other)
+            }
+            /// Panic with both values printed in full, as [`Debug`] prints the shortest digits that round-trip,
+            /// so values differing in the last place are still told apart, and the difference.
+            fn assert_approx<T: Borrow<Self> + Debug + Clone>(&self, other: T) {
+                let other = *other.borrow();
+                assert!(
+                    self.approx(other),
+                    "{self:?} != {other:?} (difference {:e})",
+                    self.approx_diff(other)
+                );
+            }
+            fn approx_distance<T: Borrow<Self>>(&self, other: T) -> f64 {
+                f64::from(self.approx_diff(other))
+            }
+        }
+
+        /// Subnormal values are below the smallest normal value, so their differences to each other and to zero
+        /// are far below the default tolerance and they are approximately equal to zero.
+        /// Rust does not enable flush-to-zero, and where the platform does, a flushed difference is `0.0`,
+        /// which only changes the result for tolerances below the smallest normal value.
+        /// Use [`ApproxFloat::approx_ulps`] to tell subnormals apart, as it compares the bit patterns.
+        impl ApproxTolerance for $float {
+            type Tolerance = Self;
+            const TOLERANCE: Self = $tolerance;
+            fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: Self) -> bool {
+                let other = *other.borrow();
+                // Equal values never reach the subtraction, so `MAX` equals itself whatever `tol` is,
+                // and `inf - inf` is NaN, so infinities are only equal to the very same infinity.
+                if *self == other {
+                    return true;
+                }
+                if self.is_infinite() || other.is_infinite() {
+                    return false;
+                }
+                (self - other).abs() <= tol
+            }
+        }
+
+        impl ApproxFloat for $float {
+            const RELATIVE_TOLERANCE: Self = $relative;
+            fn approx_relative<T: Borrow<Self>>(&self, other: T) -> bool {
+                within_relative!(*self, *other.borrow(), Self::RELATIVE_TOLERANCE)
+            }
+            fn approx_relative_to<T: Borrow<Self>>(&self, other: T, reference: Self, rel_tol: Self) -> bool {
+                let other = *other.borrow();
+                *self == other || (self - other).abs() <= reference.abs() * rel_tol
+            }
+            const NEAR_ZERO: Self = $near_zero;
+            fn approx_combined<T: Borrow<Self>>(&self, other: T) -> bool {
+                let other = *other.borrow();
+                if self.is_infinite() || other.is_infinite() {
+                    *self == other
+                } else if self.abs().max(other.abs()) < Self::NEAR_ZERO {
+                    self.approx_with_tolerance(other, Self::current_tolerance())
+                } else {
+                    self.approx_relative(other)
+                }
+            }
+            fn assert_approx_with<T: Borrow<Self> + Debug + Clone>(&self, other: T, tol: Self, msg: &str) {
+                let other = *other.borrow();
+                assert!(
+                    self.approx_with_tolerance(other, tol),
+                    "{msg}: {self:?} != {other:?} (difference {:e}, tolerance {:e})",
+                    self.approx_diff(other),
+                    tol
+                );
+            }
+            fn assert_approx_differs<T: Borrow<Self> + Debug + Clone>(&self, other: T, min_diff: Self) {
+                let other = *other.borrow();
+                let diff = self.approx_diff(other);
+                assert!(
+                    diff > min_diff,
+                    "{self:?} and {other:?} differ by {:e}, expected more than {:e}",
+                    diff,
+                    min_diff
+                );
+            }
+            fn approx_scaled<T: Borrow<Self>>(&self, other: T, factor: Self) -> bool {
+                self.approx_with_tolerance(other, Self::current_tolerance() * factor)
+            }
+            fn approx_within_percent<T: Borrow<Self>>(&self, other: T, percent: Self) -> bool {
+                let other = *other.borrow();
+                if other == 0.0 {
+                    self.approx(other)
+                } else if self.is_infinite() || other.is_infinite() {
+                    *self == other
+                } else {
+                    (self - other).abs() <= other.abs() * percent / 100.0
+                }
+            }
+            fn approx_angle<T: Borrow<Self>>(&self, other: T) -> bool {
+                use core::$float::consts::TAU;
+                let turns = (self - other.borrow()).abs() % TAU;
+                turns.min(TAU - turns) <= Self::TOLERANCE
+            }
+            fn approx_angle_deg<T: Borrow<Self>>(&self, other: T) -> bool {
+                self.to_radians().approx_angle(other.borrow().to_radians())
+            }
+            fn approx_ulps<T: Borrow<Self>>(&self, other: T, max_ulps: u32) -> bool {
+                let other = *other.borrow();
+                if self.is_nan() || other.is_nan() {
+                    return false;
+                }
+                // Map the sign-magnitude bit patterns onto a monotonic integer line with both zeros at 0.
+                let ordered = |value: Self| {
+                    let bits = value.to_bits() as $bits;
+                    if bits < 0 {
+                        <$bits>::MIN - bits
+                    } else {
+                        bits
+                    }
+                };
+                (i128::from(ordered(*self)) - i128::from(ordered(other))).unsigned_abs()
+                    <= u128::from(max_ulps)
+            }
+            fn approx_with_nan_eq<T: Borrow<Self>>(&self, other: T) -> bool {
+                let other = *other.borrow();
+                (self.is_nan() && other.is_nan()) || self.approx(other)
+            }
+            fn approx_signed_zero<T: Borrow<Self>>(&self, other: T) -> bool {
+                let other = *other.borrow();
+                if *self == 0.0 && other == 0.0 {
+                    self.is_sign_negative() == other.is_sign_negative()
+                } else {
+                    self.approx(other)
+                }
+            }
+            fn approx_cfg<T: Borrow<Self>>(&self, other: T, cfg: &ApproxConfig<Self>) -> bool {
+                let other = *other.borrow();
+                if self.is_nan() && other.is_nan() {
+                    return cfg.nan_equal;
+                }
+                *self == other
+                    || cfg.abs_tol.is_some_and(|tol| self.approx_with_tolerance(other, tol))
+                    || cfg.rel_tol.is_some_and(|tol| within_relative!(*self, other, tol))
+                    || cfg.max_ulps.is_some_and(|max_ulps| self.approx_ulps(other, max_ulps))
+            }
+            #[cfg(not(feature = "std"))]
+            fn current_tolerance() -> Self {
+                Self::TOLERANCE
+            }
+            #[cfg(feature = "std")]
+            fn current_tolerance() -> Self {
+                <Self as sealed::ToleranceOverride>::tolerance_override()
+                    .get()
+                    .unwrap_or(Self::TOLERANCE)
+            }
+        }
+
+        #[cfg(feature = "std")]
+        impl sealed::ToleranceOverride for $float {
+            fn tolerance_override() -> &'static std::thread::LocalKey<Cell<Option<Self>>> {
+                std::thread_local!(static OVERRIDE: Cell<Option<$float>> = const { Cell::new(None) });
+                &OVERRIDE
+            }
+        }
+
+        impl ApproxReport for $float {
+            type Value = Self;
+            fn try_approx<T: Borrow<Self>>(&self, other: T) -> Result<(), ReportedMismatch<Self>> {
+                let other = *other.borrow();
+                if self.approx(other) {
+                    return Ok(());
+                }
+                Err(ReportedMismatch::Values {
+                    left: *self,
+                    right: other,
+                    diff: self.approx_diff(other),
+                    relative: (self - other) / other.abs(),
+                    tolerance: Self::current_tolerance(),
+                    index: None,
+                })
+            }
+        }
+
+        /// Values that are not approximately equal, including NaN, are ordered by `total_cmp`.
+        impl ApproxOrd for $float {
+            fn approx_cmp<T: Borrow<Self>>(&self, other: T) -> Ordering {
+                let other = other.borrow();
+                if self.approx(other) {
+                    Ordering::Equal
+                } else {
+                    self.total_cmp(other)
+                }
+            }
+        }
+
+        /// The difference is `|a - b|`, zero for identical infinities and NaN if either value is NaN.
+        impl ApproxDiff for $float {
+            type Diff = Self;
+            fn approx_diff<T: Borrow<Self>>(&self, other: T) -> Self {
+                let other = *other.borrow();
+                if *self == other {
+                    0.0
+                } else {
+                    (self - other).abs()
+                }
+            }
+        }
+    )*};
+}
+
+impl_float! {
+    f32, i32 => 1e-3, 1e-5, 1e2;
+    f64, i64 => 1e-6, 1e-9, 1e3;
+}
+
+macro_rules! impl_integer {
+    ($($int:ty),* => |$this:ident, $other:ident, $tol:ident| $within:expr) => {$(
+        impl ApproxEq for $int {
+            fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+                self.approx_with_tolerance(other, Self::TOLERANCE)
+            }
+            fn approx_distance<T: Borrow<Self>>(&self, other: T) -> f64 {
+                self.abs_diff(*other.borrow()) as f64
+            }
+        }
+
+        /// Integers are exactly equal by default, and `tol` is the largest allowed absolute difference.
+        impl ApproxTolerance for $int {
+            type Tolerance = Self;
+            const TOLERANCE: Self = 0;
+            fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: Self) -> bool {
+                let ($this, $other, $tol) = (*self, *other.borrow(), tol);
+                $within
+            }
+        }
+    )*};
+}
+
+impl_integer!(u8, u16, u32, u64, u128, usize => |this, other, tol| this.abs_diff(other) <= tol);
+impl_integer!(i8, i16, i32, i64, i128, isize => |this, other, tol| {
+    tol >= 0 && this.abs_diff(other) <= tol.unsigned_abs()
+});
+
+macro_rules! impl_non_zero {
+    ($($non_zero:ty => $int:ty),* $(,)?) => {$(
+        impl ApproxEq for $non_zero {
+            fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+                self.get().approx(other.borrow().get())
+            }
+            fn approx_distance<T: Borrow<Self>>(&self, other: T) -> f64 {
+                self.get().approx_distance(other.borrow().get())
+            }
+        }
+
+        /// The values are compared like the integers they hold, with the tolerance of that integer type.
+        impl ApproxTolerance for $non_zero {
+            type Tolerance = $int;
+            const TOLERANCE: $int = <$int>::TOLERANCE;
+            fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: $int) -> bool {
+                self.get().approx_with_tolerance(other.borrow().get(), tol)
+            }
+        }
+    )*};
+}
+
+impl_non_zero! {
+    NonZeroU8 => u8,
+    NonZeroU16 => u16,
+    NonZeroU32 => u32,
+    NonZeroU64 => u64,
+    NonZeroU128 => u128,
+    NonZeroUsize => usize,
+    NonZeroI8 => i8,
+    NonZeroI16 => i16,
+    NonZeroI32 => i32,
+    NonZeroI64 => i64,
+    NonZeroI128 => i128,
+    NonZeroIsize => isize,
+}
+
+/// Booleans are approximately equal only if they are equal.
+impl ApproxEq for bool {
     fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
-        (*self - other.borrow()).abs() <= Self::splat(1e-3)
+        *self == *other.borrow()
     }
-    fn assert_approx<T: Borrow<Self> + Debug + Clone>(&self, other: T) {
-        assert!(self.approx(other.clone()), "{self:?} != {other:?}");
+}
+
+impl ApproxTolerance for bool {
+    type Tolerance = ();
+    const TOLERANCE: Self::Tolerance = ();
+    fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, _tol: Self::Tolerance) -> bool {
+        self.approx(other)
     }
 }
 
-#[cfg(feature = "simd")]
-impl ApproxEq for f64x4 {
+impl ApproxEq for Duration {
     fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
-        (*self - other.borrow()).abs() <= Self::splat(1e-6)
+        self.approx_with_tolerance(other, Self::TOLERANCE)
     }
-    fn assert_approx<T: Borrow<Self> + Debug + Clone>(&self, other: T) {
-        assert!(self.approx(other.clone()), "{self:?} != {other:?}");
+}
+
+/// Durations are approximately equal within one millisecond by default.
+///
+/// The absolute difference is taken symmetrically, so the comparison never underflows.
+impl ApproxTolerance for Duration {
+    type Tolerance = Self;
+    const TOLERANCE: Self = Self::from_millis(1);
+    fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: Self) -> bool {
+        self.abs_diff(*other.borrow()) <= tol
     }
 }
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::ops::Not;
 
-    #[test]
-    fn test_approx_eq() {
-        assert!(1.0000f32.approx(1.0001f32), "1.0000 approx 1.0001");
-        assert!(1.000f32.approx(1.001f32).not(), "1.000 not approx 1.001");
+#[cfg(feature = "std")]
+impl ApproxEq for Instant {
+    fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+        self.approx_with_tolerance(other, Self::TOLERANCE)
+    }
+}
 
-        assert!(1.000000f64.approx(1.000001f64), "1.000000 approx 1.000001");
-        assert!(
-            1.0000f64.approx(1.00001f64).not(),
-            "1.000000 not approx 1.00001"
-        );
+/// Instants are approximately equal within a slack of ten milliseconds by default.
+///
+/// Timer resolution, scheduling and system load all jitter measurements, often by more than a millisecond,
+/// so pick a tolerance from what the test machine can guarantee rather than relying on the default.
+#[cfg(feature = "std")]
+impl ApproxTolerance for Instant {
+    type Tolerance = Duration;
+    const TOLERANCE: Duration = Duration::from_millis(10);
+    fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: Duration) -> bool {
+        let other = *other.borrow();
+        (*self).max(other) - (*self).min(other) <= tol
     }
+}
 
-    #[test]
-    fn test_assert_approx_eq() {
-        1.0000f32.assert_approx(1.0001f32);
-        1.000000f64.assert_approx(1.000001f64);
+/// Run `f` with `tol` as the default tolerance of [`ApproxEq::approx`] for floats of type `F`, such as to loosen a whole test.
+///
+/// `F` is [`f32`] or [`f64`], and other [`ApproxFloat`] implementations report their own [`ApproxFloat::current_tolerance`].
+/// The override is thread-local: other threads, including those spawned by `f`, keep their own tolerance.
+/// Only the default comparisons and [`ApproxFloat::approx_scaled`] read it, so explicit tolerances such as [`ApproxTolerance::approx_with_tolerance`]
+/// and the relative comparisons are unaffected, as are floats of other types.
+/// Overrides nest, and the previous tolerance is restored when `f` returns or panics.
+///
+/// ```
+/// use approximately::{with_tolerance_override, ApproxEq};
+///
+/// assert!(!1.0f64.approx(1.01));
+/// with_tolerance_override(0.1f64, || assert!(1.0f64.approx(1.01)));
+/// assert!(!1.0f64.approx(1.01));
+/// ```
+#[cfg(feature = "std")]
+pub fn with_tolerance_override<F: ApproxFloat + sealed::ToleranceOverride, R>(
+    tol: F,
+    f: impl FnOnce() -> R,
+) -> R {
+    /// Restore the previous override on drop, so it is restored on unwinding too.
+    struct Restore<F: sealed::ToleranceOverride>(Option<F>);
+    impl<F: sealed::ToleranceOverride> Drop for Restore<F> {
+        fn drop(&mut self) {
+            F::tolerance_override().set(self.0);
+        }
+    }
+    let _restore = Restore(F::tolerance_override().replace(Some(tol)));
+    f()
+}
 
-        #[cfg(feature = "simd")]
-        {
-            f32x4::splat(1.0000).assert_approx(f32x4::splat(1.0001));
-            f64x4::splat(1.000000).assert_approx(f64x4::splat(1.000001));
+/// Evaluate if two instants are at most `within` apart, in either order.
+///
+/// To compare a measured duration against an expected one, compare the [`Duration`]s instead,
+/// such as `start.elapsed().approx_with_tolerance(expected, slack)`.
+#[cfg(feature = "std")]
+pub fn approx_elapsed(a: Instant, b: Instant, within: Duration) -> bool {
+    a.approx_with_tolerance(b, within)
+}
+
+impl<A> ApproxEq for [A]
+where
+    A: ApproxEq + Debug + Clone,
+{
+    fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+        if self.len() != other.borrow().len() {
+            return false;
         }
+        self.iter()
+            .zip(other.borrow().iter())
+            .all(|(a, b)| a.approx(b))
+    }
+    /// Panic with the lengths if they differ, otherwise with the first diverging index,
+    /// the values there, how many elements diverge and their largest [`ApproxEq::approx_distance`], instead of the whole slices.
+    fn assert_approx<T: Borrow<Self> + Debug + Clone>(&self, other: T) {
+        let other = other.borrow();
+        assert!(
+            self.len() == other.len(),
+            "{self:?} != {other:?} (lengths {} and {})",
+            self.len(),
+            other.len()
+        );
+        let mut mismatches = self
+            .iter()
+            .zip(other.iter())
+            .enumerate()
+            .filter(|(_, (a, b))| a.approx_ne(*b));
+        if let Some((index, (a, b))) = mismatches.next() {
+            let count = mismatches.count() + 1;
+            let (len, distance) = (self.len(), self.approx_distance(other));
+            match a.first_mismatch_index(b) {
+                Some(col) => panic!(
+                    "slices differ at index ({index}, {col}): {a:?} != {b:?} ({count} of {len} elements differ), max distance {distance:e}"
+                ),
+                None => panic!(
+                    "slices differ at index {index}: {a:?} != {b:?} ({count} of {len} elements differ), max distance {distance:e}"
+                ),
+            }
+        }
+    }
+    fn approx_distance<T: Borrow<Self>>(&self, other: T) -> f64 {
+        let other = other.borrow();
+        if self.len() != other.len() {
+            return f64::INFINITY;
+        }
+        max_diff(
+            self.iter()
+                .zip(other.iter())
+                .map(|(a, b)| a.approx_distance(b)),
+        )
+        .unwrap_or(0.0)
+    }
+    fn first_mismatch_index<T: Borrow<Self>>(&self, other: T) -> Option<usize> {
+        let other = other.borrow();
+        self.iter()
+            .zip(other.iter())
+            .position(|(a, b)| a.approx_ne(b))
+            .or_else(|| (self.len() != other.len()).then(|| self.len().min(other.len())))
+    }
+    #[cfg(feature = "alloc")]
+    fn first_mismatch<T: Borrow<Self>>(&self, other: T) -> Option<String> {
+        let other = other.borrow();
+        let index = self.first_mismatch_index(other)?;
+        let inner = self
+            .get(index)
+            .zip(other.get(index))
+            .and_then(|(a, b)| a.first_mismatch(b));
+        Some(format!("[{index}]{}", inner.unwrap_or_default()))
+    }
+}
+
+impl<A> ApproxTolerance for [A]
+where
+    A: ApproxTolerance + Debug + Clone,
+{
+    type Tolerance = A::Tolerance;
+    const TOLERANCE: Self::Tolerance = A::TOLERANCE;
+    fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: Self::Tolerance) -> bool {
+        if self.len() != other.borrow().len() {
+            return false;
+        }
+        self.iter()
+            .zip(other.borrow().iter())
+            .all(|(a, b)| a.approx_with_tolerance(b, tol))
+    }
+}
+
+/// The difference is the maximum element-wise difference, or [`None`] if the lengths differ or the slices are empty.
+impl<A> ApproxDiff for [A]
+where
+    A: ApproxDiff<Diff = A> + PartialOrd + Debug + Clone,
+{
+    type Diff = Option<A>;
+    fn approx_diff<T: Borrow<Self>>(&self, other: T) -> Self::Diff {
+        let other = other.borrow();
+        if self.len() != other.len() {
+            return None;
+        }
+        max_diff(self.iter().zip(other.iter()).map(|(a, b)| a.approx_diff(b)))
+    }
+}
+
+/// The report holds the elements that differ the most, where NaN differs more than any number.
+impl<A> ApproxReport for [A]
+where
+    A: ApproxReport<Value = A> + PartialOrd + Debug + Clone,
+{
+    type Value = A;
+    fn try_approx<T: Borrow<Self>>(&self, other: T) -> Result<(), ReportedMismatch<A>> {
+        let other = other.borrow();
+        if self.len() != other.len() {
+            return Err(ReportedMismatch::Lengths {
+                left: self.len(),
+                right: other.len(),
+            });
+        }
+        self.iter()
+            .zip(other.iter())
+            .enumerate()
+            .filter_map(|(index, (a, b))| {
+                let mut mismatch = a.try_approx(b).err()?;
+                if let ReportedMismatch::Values { index: at, .. } = &mut mismatch {
+                    *at = Some(index);
+                }
+                Some(mismatch)
+            })
+            .reduce(|worst, mismatch| match (&worst, &mismatch) {
+                (
+                    ReportedMismatch::Values { diff: max, .. },
+                    ReportedMismatch::Values { diff, .. },
+                ) if max.partial_cmp(max).is_some()
+                    && matches!(diff.partial_cmp(max), None | Some(Ordering::Greater)) =>
+                {
+                    mismatch
+                }
+                _ => worst,
+            })
+            .map_or(Ok(()), Err)
+    }
+}
+
+impl<A> ApproxSlice for [A]
+where
+    A: ApproxEq + Debug + Clone,
+{
+    fn approx_prefix<T: Borrow<Self>>(&self, other: T) -> bool {
+        self.iter()
+            .zip(other.borrow().iter())
+            .all(|(a, b)| a.approx(b))
+    }
+}
+
+impl<A, const N: usize> ApproxEq for [A; N]
+where
+    A: ApproxEq + Debug + Clone,
+{
+    fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+        self[..].approx(&other.borrow()[..])
+    }
+    fn assert_approx<T: Borrow<Self> + Debug + Clone>(&self, other: T) {
+        self[..].assert_approx(&other.borrow()[..]);
+    }
+    fn first_mismatch_index<T: Borrow<Self>>(&self, other: T) -> Option<usize> {
+        self[..].first_mismatch_index(&other.borrow()[..])
+    }
+    #[cfg(feature = "alloc")]
+    fn first_mismatch<T: Borrow<Self>>(&self, other: T) -> Option<String> {
+        self[..].first_mismatch(&other.borrow()[..])
+    }
+    fn approx_distance<T: Borrow<Self>>(&self, other: T) -> f64 {
+        self[..].approx_distance(&other.borrow()[..])
+    }
+}
+
+impl<A, const N: usize> ApproxTolerance for [A; N]
+where
+    A: ApproxTolerance + Debug + Clone,
+{
+    type Tolerance = A::Tolerance;
+    const TOLERANCE: Self::Tolerance = A::TOLERANCE;
+    fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: Self::Tolerance) -> bool {
+        self[..].approx_with_tolerance(&other.borrow()[..], tol)
+    }
+}
+
+impl<A, const N: usize> ApproxReport for [A; N]
+where
+    A: ApproxReport<Value = A> + PartialOrd + Debug + Clone,
+{
+    type Value = A;
+    fn try_approx<T: Borrow<Self>>(&self, other: T) -> Result<(), ReportedMismatch<A>> {
+        self[..].try_approx(&other.borrow()[..])
+    }
+}
+
+impl<A, const N: usize> ApproxDiff for [A; N]
+where
+    A: ApproxDiff<Diff = A> + PartialOrd + Debug + Clone,
+{
+    type Diff = Option<A>;
+    fn approx_diff<T: Borrow<Self>>(&self, other: T) -> Self::Diff {
+        self[..].approx_diff(&other.borrow()[..])
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<A> ApproxEq for Vec<A>
+where
+    A: ApproxEq + Debug + Clone,
+{
+    fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+        self[..].approx(&other.borrow()[..])
+    }
+    fn assert_approx<T: Borrow<Self> + Debug + Clone>(&self, other: T) {
+        self[..].assert_approx(&other.borrow()[..]);
+    }
+    fn first_mismatch_index<T: Borrow<Self>>(&self, other: T) -> Option<usize> {
+        self[..].first_mismatch_index(&other.borrow()[..])
+    }
+    #[cfg(feature = "alloc")]
+    fn first_mismatch<T: Borrow<Self>>(&self, other: T) -> Option<String> {
+        self[..].first_mismatch(&other.borrow()[..])
+    }
+    fn approx_distance<T: Borrow<Self>>(&self, other: T) -> f64 {
+        self[..].approx_distance(&other.borrow()[..])
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<A> ApproxTolerance for Vec<A>
+where
+    A: ApproxTolerance + Debug + Clone,
+{
+    type Tolerance = A::Tolerance;
+    const TOLERANCE: Self::Tolerance = A::TOLERANCE;
+    fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: Self::Tolerance) -> bool {
+        self[..].approx_with_tolerance(&other.borrow()[..], tol)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<A> ApproxReport for Vec<A>
+where
+    A: ApproxReport<Value = A> + PartialOrd + Debug + Clone,
+{
+    type Value = A;
+    fn try_approx<T: Borrow<Self>>(&self, other: T) -> Result<(), ReportedMismatch<A>> {
+        self[..].try_approx(&other.borrow()[..])
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<A> ApproxDiff for Vec<A>
+where
+    A: ApproxDiff<Diff = A> + PartialOrd + Debug + Clone,
+{
+    type Diff = Option<A>;
+    fn approx_diff<T: Borrow<Self>>(&self, other: T) -> Self::Diff {
+        self[..].approx_diff(&other.borrow()[..])
+    }
+}
+
+macro_rules! impl_pointer {
+    ($($(#[$attr:meta])* $pointer:ident),* $(,)?) => {$(
+        $(#[$attr])*
+        impl<A> ApproxEq for $pointer<A>
+        where
+            A: ApproxEq + ?Sized,
+        {
+            fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+                (**self).approx(&**other.borrow())
+            }
+            fn first_mismatch<T: Borrow<Self>>(&self, other: T) -> Option<String> {
+                (**self).first_mismatch(&**other.borrow())
+            }
+        }
+
+        $(#[$attr])*
+        impl<A> ApproxTolerance for $pointer<A>
+        where
+            A: ApproxTolerance + ?Sized,
+        {
+            type Tolerance = A::Tolerance;
+            const TOLERANCE: Self::Tolerance = A::TOLERANCE;
+            fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: Self::Tolerance) -> bool {
+                (**self).approx_with_tolerance(&**other.borrow(), tol)
+            }
+        }
+    )*};
+}
+
+/// Access the endpoints of a range.
+trait Endpoints {
+    type Endpoint;
+    fn endpoints(&self) -> (&Self::Endpoint, &Self::Endpoint);
+}
+
+/// Name the first endpoint of the ranges that diverges.
+fn range_mismatch<R: Endpoints>(
+    range: &R,
+    other: &R,
+    approx: impl Fn(&R::Endpoint, &R::Endpoint) -> bool,
+) -> Option<&'static str> {
+    let ((start_a, end_a), (start_b, end_b)) = (range.endpoints(), other.endpoints());
+    if !approx(start_a, start_b) {
+        Some("start")
+    } else if !approx(end_a, end_b) {
+        Some("end")
+    } else {
+        None
+    }
+}
+
+macro_rules! impl_range {
+    ($($range:ident => |$this:ident| $endpoints:expr),* $(,)?) => {$(
+        impl<A> Endpoints for $range<A> {
+            type Endpoint = A;
+            fn endpoints(&self) -> (&A, &A) {
+                let $this = self;
+                $endpoints
+            }
+        }
+
+        /// Compare both endpoints.
+        impl<A: ApproxEq> ApproxEq for $range<A> {
+            fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+                range_mismatch(self, other.borrow(), |a, b| a.approx(b)).is_none()
+            }
+            fn assert_approx<T: Borrow<Self> + Debug + Clone>(&self, other: T) {
+                if let Some(endpoint) = range_mismatch(self, other.borrow(), |a, b| a.approx(b)) {
+                    panic!("{self:?} != {other:?} ({endpoint} differs)");
+                }
+            }
+        }
+
+        impl<A: ApproxTolerance> ApproxTolerance for $range<A> {
+            type Tolerance = A::Tolerance;
+            const TOLERANCE: Self::Tolerance = A::TOLERANCE;
+            fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: Self::Tolerance) -> bool {
+                range_mismatch(self, other.borrow(), |a, b| a.approx_with_tolerance(b, tol)).is_none()
+            }
+        }
+    )*};
+}
+
+impl_range! {
+    Range => |range| (&range.start, &range.end),
+    RangeInclusive => |range| (range.start(), range.end()),
+}
+
+macro_rules! impl_atomic {
+    ($($(#[$attr:meta])* $atomic:ident => $int:ty),* $(,)?) => {$(
+        /// Load both values with [`atomic::Ordering::SeqCst`] and compare them like integers.
+        $(#[$attr])*
+        impl ApproxEq for atomic::$atomic {
+            fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+                self.approx_with_tolerance(other, Self::TOLERANCE)
+            }
+        }
+
+        $(#[$attr])*
+        impl ApproxTolerance for atomic::$atomic {
+            type Tolerance = $int;
+            const TOLERANCE: $int = <$int>::TOLERANCE;
+            fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: $int) -> bool {
+                let ordering = atomic::Ordering::SeqCst;
+                self.load(ordering).approx_with_tolerance(other.borrow().load(ordering), tol)
+            }
+        }
+
+        $(#[$attr])*
+        impl sealed::Sealed for atomic::$atomic {}
+
+        $(#[$attr])*
+        impl AtomicInteger for atomic::$atomic {
+            type Value = $int;
+            fn load_seq_cst(&self) -> $int {
+                self.load(atomic::Ordering::SeqCst)
+            }
+        }
+    )*};
+}
+
+mod sealed {
+    #[cfg(feature = "std")]
+    use core::cell::Cell;
+
+    pub trait Sealed {}
+
+    /// The thread-local override behind [`crate::with_tolerance_override`], only for [`f32`] and [`f64`].
+    #[cfg(feature = "std")]
+    pub trait ToleranceOverride: Copy + 'static {
+        fn tolerance_override() -> &'static std::thread::LocalKey<Cell<Option<Self>>>;
+    }
+}
+
+/// The integer atomics of [`core::sync::atomic`], compared by [`approx_atomic`].
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+pub trait AtomicInteger: sealed::Sealed {
+    /// The integer type stored in the atomic.
+    type Value: ApproxTolerance<Tolerance = Self::Value>;
+    /// Load the value with [`atomic::Ordering::SeqCst`].
+    fn load_seq_cst(&self) -> Self::Value;
+}
+
+impl_atomic! {
+    #[cfg(target_has_atomic = "8")]
+    AtomicU8 => u8,
+    #[cfg(target_has_atomic = "8")]
+    AtomicI8 => i8,
+    #[cfg(target_has_atomic = "16")]
+    AtomicU16 => u16,
+    #[cfg(target_has_atomic = "16")]
+    AtomicI16 => i16,
+    #[cfg(target_has_atomic = "32")]
+    AtomicU32 => u32,
+    #[cfg(target_has_atomic = "32")]
+    AtomicI32 => i32,
+    #[cfg(target_has_atomic = "64")]
+    AtomicU64 => u64,
+    #[cfg(target_has_atomic = "64")]
+    AtomicI64 => i64,
+    #[cfg(target_has_atomic = "ptr")]
+    AtomicUsize => usize,
+    #[cfg(target_has_atomic = "ptr")]
+    AtomicIsize => isize,
+}
+
+/// Evaluate if the current values of two atomics are at most `tol` apart, such as shared counters in tests.
+///
+/// Both values are loaded with [`atomic::Ordering::SeqCst`], one after the other,
+/// so concurrent writers may change either value between the loads.
+pub fn approx_atomic<A: AtomicInteger>(a: &A, b: &A, tol: A::Value) -> bool {
+    a.load_seq_cst()
+        .approx_with_tolerance(b.load_seq_cst(), tol)
+}
+
+/// Compare the current values.
+impl<A: ApproxEq + Copy> ApproxEq for Cell<A> {
+    fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+        self.get().approx(other.borrow().get())
+    }
+}
+
+impl<A: ApproxTolerance + Copy> ApproxTolerance for Cell<A> {
+    type Tolerance = A::Tolerance;
+    const TOLERANCE: Self::Tolerance = A::TOLERANCE;
+    fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: Self::Tolerance) -> bool {
+        self.get().approx_with_tolerance(other.borrow().get(), tol)
+    }
+}
+
+/// Compare the borrowed values.
+///
+/// # Panics
+///
+/// Panics if either value is currently mutably borrowed, like [`RefCell::borrow`].
+impl<A: ApproxEq + ?Sized> ApproxEq for RefCell<A> {
+    fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+        (*self.borrow()).approx(&*other.borrow().borrow())
+    }
+}
+
+impl<A: ApproxTolerance + ?Sized> ApproxTolerance for RefCell<A> {
+    type Tolerance = A::Tolerance;
+    const TOLERANCE: Self::Tolerance = A::TOLERANCE;
+    fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: Self::Tolerance) -> bool {
+        (*self.borrow()).approx_with_tolerance(&*other.borrow().borrow(), tol)
+    }
+}
+
+/// Forward to the value of a single-field wrapper.
+macro_rules! impl_newtype {
+    ($($newtype:ident),* $(,)?) => {$(
+        impl<A: ApproxEq> ApproxEq for $newtype<A> {
+            fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+                self.0.approx(&other.borrow().0)
+            }
+        }
+
+        impl<A: ApproxTolerance> ApproxTolerance for $newtype<A> {
+            type Tolerance = A::Tolerance;
+            const TOLERANCE: Self::Tolerance = A::TOLERANCE;
+            fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: Self::Tolerance) -> bool {
+                self.0.approx_with_tolerance(&other.borrow().0, tol)
+            }
+        }
+    )*};
+}
+
+impl_newtype!(Wrapping, Saturating, Reverse);
+
+/// Compare the referenced values, so values yielded by iterators and closures need no dereferencing.
+impl<A> ApproxEq for &A
+where
+    A: ApproxEq + ?Sized,
+{
+    fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+        (**self).approx(*other.borrow())
+    }
+    fn assert_approx<T: Borrow<Self> + Debug + Clone>(&self, other: T) {
+        (**self).assert_approx(*other.borrow());
+    }
+    #[cfg(feature = "alloc")]
+    fn first_mismatch<T: Borrow<Self>>(&self, other: T) -> Option<String> {
+        (**self).first_mismatch(*other.borrow())
+    }
+}
+
+impl<A> ApproxTolerance for &A
+where
+    A: ApproxTolerance + ?Sized,
+{
+    type Tolerance = A::Tolerance;
+    const TOLERANCE: Self::Tolerance = A::TOLERANCE;
+    fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: Self::Tolerance) -> bool {
+        (**self).approx_with_tolerance(*other.borrow(), tol)
+    }
+}
+
+impl_pointer! {
+    #[cfg(feature = "alloc")]
+    Box,
+    #[cfg(feature = "alloc")]
+    Rc,
+    #[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+    Arc,
+}
+
+/// Compare the contents, whether they are borrowed or owned.
+#[cfg(feature = "alloc")]
+impl<B> ApproxEq for Cow<'_, B>
+where
+    B: ApproxEq + ToOwned + ?Sized,
+    B::Owned: Debug,
+{
+    fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+        (**self).approx(&**other.borrow())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<B> ApproxTolerance for Cow<'_, B>
+where
+    B: ApproxTolerance + ToOwned + ?Sized,
+    B::Owned: Debug,
+{
+    type Tolerance = B::Tolerance;
+    const TOLERANCE: Self::Tolerance = B::TOLERANCE;
+    fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: Self::Tolerance) -> bool {
+        (**self).approx_with_tolerance(&**other.borrow(), tol)
+    }
+}
+
+/// Two [`Some`]s compare their contents and two [`None`]s are equal, so options compose with the other impls:
+/// `Option<&A>` compares through the reference and `Option<Option<A>>` compares level by level.
+impl<A> ApproxEq for Option<A>
+where
+    A: ApproxEq + Debug + Clone,
+{
+    fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+        match (self, other.borrow()) {
+            (Some(a), Some(b)) => a.approx(b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+    fn assert_approx<T: Borrow<Self> + Debug + Clone>(&self, other: T) {
+        assert!(self.approx(other.clone()), "{self:?} != {other:?}");
+    }
+    #[cfg(feature = "alloc")]
+    fn first_mismatch<T: Borrow<Self>>(&self, other: T) -> Option<String> {
+        match (self, other.borrow()) {
+            (Some(a), Some(b)) => a.first_mismatch(b),
+            (None, None) => None,
+            _ => Some(String::new()),
+        }
+    }
+}
+
+impl<A> ApproxTolerance for Option<A>
+where
+    A: ApproxTolerance + Debug + Clone,
+{
+    type Tolerance = A::Tolerance;
+    const TOLERANCE: Self::Tolerance = A::TOLERANCE;
+    fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: Self::Tolerance) -> bool {
+        match (self, other.borrow()) {
+            (Some(a), Some(b)) => a.approx_with_tolerance(b, tol),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl<A, E> ApproxEq for Result<A, E>
+where
+    A: ApproxEq + Debug + Clone,
+    E: PartialEq + Debug + Clone,
+{
+    fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+        match (self, other.borrow()) {
+            (Ok(a), Ok(b)) => a.approx(b),
+            (Err(a), Err(b)) => a == b,
+            _ => false,
+        }
+    }
+    fn assert_approx<T: Borrow<Self> + Debug + Clone>(&self, other: T) {
+        assert!(self.approx(other.clone()), "{self:?} != {other:?}");
+    }
+}
+
+impl<A, E> ApproxTolerance for Result<A, E>
+where
+    A: ApproxTolerance + Debug + Clone,
+    E: PartialEq + Debug + Clone,
+{
+    type Tolerance = A::Tolerance;
+    const TOLERANCE: Self::Tolerance = A::TOLERANCE;
+    fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: Self::Tolerance) -> bool {
+        match (self, other.borrow()) {
+            (Ok(a), Ok(b)) => a.approx_with_tolerance(b, tol),
+            (Err(a), Err(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+macro_rules! impl_tuple {
+    ($(($($name:ident $index:tt),+))*) => {$(
+        impl<$($name),+> ApproxEq for ($($name,)+)
+        where
+            $($name: ApproxEq + Debug + Clone,)+
+        {
+            fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+                let other = other.borrow();
+                $(self.$index.approx(&other.$index))&&+
+            }
+            fn assert_approx<T: Borrow<Self> + Debug + Clone>(&self, other: T) {
+                let borrowed = other.borrow();
+                $(
+                    assert!(
+                        self.$index.approx(&borrowed.$index),
+                        "{self:?} != {other:?} (index {} differs)",
+                        stringify!($index)
+                    );
+                )+
+            }
+            #[cfg(feature = "alloc")]
+            fn first_mismatch<T: Borrow<Self>>(&self, other: T) -> Option<String> {
+                let other = other.borrow();
+                $(
+                    if let Some(path) = self.$index.first_mismatch(&other.$index) {
+                        return Some(format!(".{}{path}", stringify!($index)));
+                    }
+                )+
+                None
+            }
+        }
+
+        impl<$($name),+> ApproxTolerance for ($($name,)+)
+        where
+            $($name: ApproxTolerance + Debug + Clone,)+
+        {
+            type Tolerance = ($($name::Tolerance,)+);
+            const TOLERANCE: Self::Tolerance = ($($name::TOLERANCE,)+);
+            fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: Self::Tolerance) -> bool {
+                let other = other.borrow();
+                $(self.$index.approx_with_tolerance(&other.$index, tol.$index))&&+
+            }
+        }
+    )*};
+}
+
+impl_tuple! {
+    (A 0)
+    (A 0, B 1)
+    (A 0, B 1, C 2)
+    (A 0, B 1, C 2, D 3)
+    (A 0, B 1, C 2, D 3, E 4)
+    (A 0, B 1, C 2, D 3, E 4, F 5)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11)
+}
+
+/// The unit value is always approximately equal to itself.
+impl ApproxEq for () {
+    fn approx<T: Borrow<Self>>(&self, _other: T) -> bool {
+        true
+    }
+}
+
+impl ApproxTolerance for () {
+    type Tolerance = ();
+    const TOLERANCE: Self::Tolerance = ();
+    fn approx_with_tolerance<T: Borrow<Self>>(&self, _other: T, _tol: Self::Tolerance) -> bool {
+        true
+    }
+}
+
+/// Markers hold no value, so they are always approximately equal, whatever `A` is.
+impl<A: ?Sized> ApproxEq for PhantomData<A> {
+    fn approx<T: Borrow<Self>>(&self, _other: T) -> bool {
+        true
+    }
+}
+
+impl<A: ?Sized> ApproxTolerance for PhantomData<A> {
+    type Tolerance = ();
+    const TOLERANCE: Self::Tolerance = ();
+    fn approx_with_tolerance<T: Borrow<Self>>(&self, _other: T, _tol: Self::Tolerance) -> bool {
+        true
+    }
+}
+
+/// Lane-wise comparisons for portable SIMD vectors.
+///
+/// Every lane is compared against the tolerance on its own, producing a mask.
+/// [`ApproxEq::approx`] reduces that mask with `all`, so the vectors are equal only if every lane is,
+/// while [`ApproxSimd::approx_any`] reduces it with `any`.
+#[cfg(feature = "simd")]
+pub trait ApproxSimd: ApproxTolerance<Tolerance = Self> + Copy {
+    /// Evaluate if at least one lane is approximately equal.
+    fn approx_any<T: Borrow<Self>>(&self, other: T) -> bool;
+    /// Evaluate if every lane is approximately equal within the tolerance of the same lane in `tol`.
+    ///
+    /// Use it when the lanes hold different quantities, such as the `x`, `y`, `z` and `w` of a vector.
+    /// This is [`ApproxTolerance::approx_with_tolerance`], which takes a vector instead of a scalar splat.
+    fn approx_with_lane_tolerance<T: Borrow<Self>>(&self, other: T, tol: Self) -> bool {
+        self.approx_with_tolerance(other, tol)
+    }
+}
+
+/// Compare arrays whose length matches a SIMD lane count by loading them into vectors.
+///
+/// [`ApproxEq::approx`] for arrays can not be specialized for these lengths on stable Rust,
+/// so this is a separate method that yields the same results as the scalar element loop:
+/// it honors [`with_tolerance_override`] and the `relative-default` feature,
+/// and an infinity is only equal to the same infinity.
+#[cfg(feature = "simd")]
+pub trait ApproxArraySimd: ApproxEq {
+    /// Evaluate if the arrays are approximately equal, comparing all lanes at once.
+    fn approx_simd<T: Borrow<Self>>(&self, other: T) -> bool;
+}
+
+/// The lane masks behind the comparisons of SIMD vectors.
+#[cfg(feature = "simd")]
+trait ApproxLanes: SimdPartialEq {
+    /// Mark the lanes within `tol`, following the scalar floats:
+    /// equal lanes always match, even infinities, and any other lane holding an infinity never does.
+    fn within_lanes(&self, other: &Self, tol: Self) -> Self::Mask;
+    /// Mark the lanes that [`ApproxEq::approx`] of the scalar floats accepts,
+    /// with the current tolerance and, with `relative-default`, the combined comparison.
+    fn approx_lanes(&self, other: &Self) -> Self::Mask;
+}
+
+#[cfg(feature = "simd")]
+macro_rules! impl_simd {
+    ($($scalar:ty => $($simd:ty),+;)*) => {$($(
+        impl ApproxLanes for $simd {
+            fn within_lanes(&self, other: &Self, tol: Self) -> Self::Mask {
+                // `inf - inf` is NaN, so equal infinities need the equality mask.
+                self.simd_eq(*other)
+                    | ((*self - other).abs().simd_le(tol) & self.is_finite() & other.is_finite())
+            }
+            #[cfg(not(feature = "relative-default"))]
+            fn approx_lanes(&self, other: &Self) -> Self::Mask {
+                self.within_lanes(other, Self::splat(<$scalar>::current_tolerance()))
+            }
+            #[cfg(feature = "relative-default")]
+            fn approx_lanes(&self, other: &Self) -> Self::Mask {
+                let max = self.abs().simd_max(other.abs());
+                let tol = max.simd_lt(Self::splat(<$scalar>::NEAR_ZERO)).select(
+                    Self::splat(<$scalar>::current_tolerance()),
+                    max * Self::splat(<$scalar>::RELATIVE_TOLERANCE),
+                );
+                self.within_lanes(other, tol)
+            }
+        }
+
+        impl ApproxTolerance for $simd {
+            type Tolerance = Self;
+            const TOLERANCE: Self = Self::splat(<$scalar>::TOLERANCE);
+            fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: Self) -> bool {
+                self.within_lanes(other.borrow(), tol).all()
+            }
+        }
+
+        impl ApproxEq for $simd {
+            fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+                self.approx_lanes(other.borrow()).all()
+            }
+            fn assert_approx<T: Borrow<Self> + Debug + Clone>(&self, other: T) {
+                assert!(self.approx(other.clone()), "{self:?} != {other:?}");
+            }
+        }
+
+        impl ApproxArraySimd for [$scalar; <$simd>::LEN] {
+            fn approx_simd<T: Borrow<Self>>(&self, other: T) -> bool {
+                <$simd>::from_array(*self).approx(<$simd>::from_array(*other.borrow()))
+            }
+        }
+
+        impl ApproxSimd for $simd {
+            fn approx_any<T: Borrow<Self>>(&self, other: T) -> bool {
+                self.approx_lanes(other.borrow()).any()
+            }
+        }
+    )+)*};
+}
+
+// Every width the portable SIMD module provides, all with the tolerance of the scalar type.
+#[cfg(feature = "simd")]
+impl_simd! {
+    f32 => f32x1, f32x2, f32x4, f32x8, f32x16, f32x32, f32x64;
+    f64 => f64x1, f64x2, f64x4, f64x8, f64x16, f64x32, f64x64;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::ops::Not;
+
+    #[test]
+    fn test_approx_eq() {
+        assert!(1.0000f32.approx(1.0001f32), "1.0000 approx 1.0001");
+        assert!(1.000f32.approx(1.001f32).not(), "1.000 not approx 1.001");
+
+        assert!(1.000000f64.approx(1.000001f64), "1.000000 approx 1.000001");
+        assert!(
+            1.0000f64.approx(1.00001f64).not(),
+            "1.000000 not approx 1.00001"
+        );
+    }
+
+    #[test]
+    fn test_approx_ne() {
+        assert!(1.0f32.approx_ne(1.01f32));
+        assert!(1.0f32.approx_ne(1.0001f32).not());
+
+        let a = [1.0f64, 2.0];
+        assert!(a[..].approx_ne(&[1.0, 2.1][..]));
+        assert!(a[..].approx_ne(&a[..]).not());
+
+        assert!(Some(1.0f64).approx_ne(None));
+        assert!(Some(1.0f64).approx_ne(Some(1.0000001)).not());
+    }
+
+    #[test]
+    fn test_assert_approx_macros() {
+        let a = 1.0f64;
+        assert_approx!(a, 1.0000001);
+        assert_approx!([1.0f32, 2.0], [1.0001, 2.0], "arrays differ");
+        assert_approx_ne!(a, 1.1);
+        assert_approx_ne!(Some(a), None, "{} should not match None", a);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion `a approx a + 0.1` failed: off by 0.1\n  left: 1.0\n right: 1.1"
+    )]
+    fn test_assert_approx_macro_message() {
+        let a = 1.0f64;
+        assert_approx!(a, a + 0.1, "off by {}", 0.1);
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion `a not approx 1.0000001` failed")]
+    fn test_assert_approx_ne_macro_message() {
+        let a = 1.0f64;
+        assert_approx_ne!(a, 1.0000001);
+    }
+
+    #[test]
+    fn test_assert_approx_eq() {
+        1.0000f32.assert_approx(1.0001f32);
+        1.000000f64.assert_approx(1.000001f64);
+
+        #[cfg(feature = "simd")]
+        {
+            f32x4::splat(1.0000).assert_approx(f32x4::splat(1.0001));
+            f64x4::splat(1.000000).assert_approx(f64x4::splat(1.000001));
+        }
+    }
+
+    #[test]
+    fn test_default_tolerance() {
+        assert_eq!(
+            format!("comparing with tolerance {:e}", f64::default_tolerance()),
+            "comparing with tolerance 1e-6"
+        );
+        assert_eq!(f32::default_tolerance(), 1e-3);
+        assert_eq!(u8::default_tolerance(), 0);
+        assert_eq!(Duration::default_tolerance(), Duration::from_millis(1));
+        assert_eq!(<[f64; 2]>::default_tolerance(), 1e-6);
+    }
+
+    #[test]
+    fn test_tolerance() {
+        #[derive(Debug, Clone, Copy)]
+        struct Strict(f64);
+
+        impl ApproxEq for Strict {
+            fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+                self.approx_with_tolerance(other, Self::TOLERANCE)
+            }
+        }
+
+        impl ApproxTolerance for Strict {
+            type Tolerance = f64;
+            const TOLERANCE: f64 = 1e-9;
+            fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: f64) -> bool {
+                self.0.approx_with_tolerance(other.borrow().0, tol)
+            }
+        }
+
+        assert_eq!(f32::TOLERANCE, 1e-3);
+        assert_eq!(f64::TOLERANCE, 1e-6);
+        assert!(Strict(1.0).approx(Strict(1.0 + 1e-10)));
+        assert!(Strict(1.0).approx(Strict(1.0 + 1e-7)).not());
+    }
+
+    #[test]
+    fn test_approx_with_tolerance() {
+        assert!(1.0f64.approx_with_tolerance(1.005, 1e-2));
+        assert!(1.0f64.approx_with_tolerance(1.0 + 1e-8, 1e-9).not());
+        assert!(1.0f32.approx_with_tolerance(1.05, 1e-1));
+
+        let a = [1.0f64, 2.0, 3.0];
+        let b = [1.005f64, 2.0, 2.995];
+        assert!(a[..].approx_with_tolerance(&b[..], 1e-2));
+        assert!(a[..].approx_with_tolerance(&b[..], 1e-3).not());
+        assert!(a[..].approx_with_tolerance(&b[..2], 1e-2).not());
+
+        assert!(Some(1.0f64).approx_with_tolerance(Some(1.005), 1e-2));
+        assert!(Some(1.0f64).approx_with_tolerance(None, 1e-2).not());
+    }
+
+    #[test]
+    fn test_approx_option_composed() {
+        let values = [1.0f64, 3.0, 2.0];
+        let max = values.iter().max_by(|a, b| a.total_cmp(b));
+        assert!(max.approx(Some(&3.0000001)));
+        assert!(max.approx(Some(&3.1)).not());
+        assert!(max.approx(None).not());
+        assert!(max.approx_with_tolerance(Some(&3.1), 0.2));
+
+        assert!(Some(None::<f64>).approx(Some(None)));
+        assert!(Some(Some(1.0f64)).approx(Some(Some(1.0000001))));
+        assert!(Some(Some(1.0f64)).approx(Some(Some(1.0001))).not());
+        assert!(Some(Some(1.0f32)).approx(Some(Some(1.0001))));
+        assert!(Some(Some(1.0f64)).approx(Some(None)).not());
+        assert!(Some(None::<f64>).approx(None).not());
+        assert!(Some(Some(1.0f64)).approx_with_tolerance(Some(Some(1.0001)), 1e-3));
+    }
+
+    #[test]
+    fn test_approx_option_tolerance() {
+        let tol = 0.1;
+        assert!(Some(1.0f64).approx_with_tolerance(Some(1.05), tol));
+        assert!(Some(1.0f64).approx_with_tolerance(Some(1.5), tol).not());
+        assert!(Some(1.0f64).approx_with_tolerance(None, tol).not());
+        assert!(None.approx_with_tolerance(Some(1.0f64), tol).not());
+        assert!(None::<f64>.approx_with_tolerance(None, tol));
+        assert!(None::<f64>.approx_with_tolerance(None, 0.0));
+        assert!(None::<f64>.approx_with_tolerance(None, f64::NAN));
+    }
+
+    #[test]
+    fn test_approx_relative() {
+        #[cfg(not(feature = "relative-default"))]
+        assert!(1e9f64.approx(1e9 + 1.0).not());
+        assert!(1e9f64.approx_relative(1e9 + 0.5));
+        assert!(1e9f64.approx_relative(1e9 + 10.0).not());
+        assert!(1e6f32.approx_relative(1e6 + 1.0 / 64.0));
+        assert!(0.0f64.approx_relative(0.0));
+        assert!(0.0f64.approx_relative(-0.0));
+        assert!(0.0f64.approx_relative(1e-12).not());
+        assert!(1.0f64.approx_relative(f64::INFINITY).not());
+        assert!(f64::NEG_INFINITY.approx_relative(-1.0).not());
+        assert!(f32::MAX.approx_relative(f32::INFINITY).not());
+        assert!(f64::INFINITY.approx_relative(f64::NEG_INFINITY).not());
+        assert!(f64::INFINITY.approx_relative(f64::INFINITY));
+    }
+
+    #[test]
+    fn test_approx_symmetric() {
+        let values = [
+            0.0f64,
+            -0.0,
+            1.0,
+            1.0 + 1e-9,
+            1.0 + 2e-9,
+            -1.0,
+            1e9,
+            1e9 + 1.0,
+            1e-300,
+            f64::MAX,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            f64::NAN,
+        ];
+        for a in values {
+            for b in values {
+                assert_eq!(a.approx_symmetric(b), b.approx_symmetric(a), "{a} and {b}");
+            }
+        }
+        assert!(1e9f64.approx_symmetric(1e9 + 0.5));
+        assert!(1.0f32.approx_symmetric(1.001).not());
+        assert!(f64::MAX.approx_symmetric(f64::INFINITY).not());
+        assert!(f64::INFINITY.approx_symmetric(f64::NEG_INFINITY).not());
+    }
+
+    #[test]
+    fn test_approx_relative_to() {
+        assert!(1.0f64.approx_relative_to(1.5, 1000.0, 1e-3));
+        assert!(1.0f64.approx_relative_to(1.5, 1000.0, 1e-4).not());
+        assert!(1.0f64.approx_relative_to(1.5, 1.0, 1e-3).not());
+        assert!(1e6f32.approx_relative_to(1e6 + 64.0, -1e9, 1e-6));
+        assert!(0.0f64.approx_relative_to(0.0, 0.0, 1e-9));
+        assert!(0.0f64.approx_relative_to(1e-300, 0.0, 1e-9).not());
+    }
+
+    #[test]
+    fn test_approx_combined() {
+        assert_eq!(f32::NEAR_ZERO, 1e2);
+        assert_eq!(f64::NEAR_ZERO, 1e3);
+        assert!(1e-8f64.approx_relative(2e-8).not());
+        assert!(1e-8f64.approx_combined(2e-8));
+        assert!(0.0f64.approx_combined(1e-7));
+        assert!(0.0f64.approx_combined(1e-5).not());
+        assert!(1e9f64.approx_combined(1e9 + 0.5));
+        assert!(1e9f64.approx_combined(1e9 + 10.0).not());
+        assert!(1e6f32.approx_combined(1e6 + 1.0));
+        assert!(1e6f32.approx_combined(1e6 + 64.0).not());
+        assert!(f64::INFINITY.approx_combined(f64::INFINITY));
+        assert!(f64::INFINITY.approx_combined(f64::MAX).not());
+        assert!(f64::NAN.approx_combined(f64::NAN).not());
+    }
+
+    #[test]
+    fn test_approx_as() {
+        assert_eq!(<Eps<-6> as Epsilon<f64>>::TOLERANCE, 1e-6);
+        assert_eq!(<Eps<-3> as Epsilon<f32>>::TOLERANCE, 1e-3);
+        assert_eq!(<Eps<2> as Epsilon<f64>>::TOLERANCE, 100.0);
+        assert!(1.0f64.approx_as::<Tight, _>(1.0 + 1e-13));
+        assert!(1.0f64.approx_as::<Tight, _>(1.0 + 1e-9).not());
+        assert!(1.0f64.approx_as::<Loose, _>(1.00005));
+        assert!(1.0f32.approx_as::<Loose, _>(1.005));
+        assert!(1.0f32.approx_as::<Tight, _>(1.005).not());
+        assert!(1.0f64.approx_as::<Eps<-1>, _>(1.05));
+        assert!(1.0f64.approx_as::<Eps<-2>, _>(1.05).not());
+    }
+
+    #[test]
+    fn test_assert_approx_with() {
+        1.0f64.assert_approx_with(1.05, 0.1, "loose bound");
+        1.0f32.assert_approx_with(1.0, 0.0, "exact");
+    }
+
+    #[test]
+    #[should_panic(expected = "integrated energy: 1.0 != 1.25 (difference 2.5e-1, tolerance 1e-1)")]
+    fn test_assert_not_approx_with() {
+        1.0f64.assert_approx_with(1.25, 0.1, "integrated energy");
+    }
+
+    #[test]
+    #[should_panic(expected = "1.0001 != 1.0012 (difference 1.09")]
+    fn test_assert_not_approx_precise() {
+        1.0001f32.assert_approx(1.0012);
+    }
+
+    #[test]
+    #[should_panic(expected = "tight: 1.0000001 != 1.0000002 (difference 9.9")]
+    fn test_assert_not_approx_with_precise() {
+        1.0000001f64.assert_approx_with(1.0000002, 0.0, "tight");
+    }
+
+    #[test]
+    #[should_panic(expected = "slices differ at index 1: 2.0000001 != 2.0000102")]
+    fn test_assert_not_approx_slice_precise() {
+        [1.0f64, 2.0000001].assert_approx([1.0, 2.0000102]);
+    }
+
+    #[test]
+    fn test_assert_approx_differs() {
+        1.0f64.assert_approx_differs(1.5, 0.1);
+        (-1.0f32).assert_approx_differs(1.0, 1.0);
+        f64::INFINITY.assert_approx_differs(f64::MAX, 1e300);
+    }
+
+    #[test]
+    #[should_panic(expected = "1.0 and 1.0625 differ by 6.25e-2, expected more than 1.25e-1")]
+    fn test_assert_approx_differs_panic() {
+        1.0f64.assert_approx_differs(1.0625, 0.125);
+    }
+
+    #[test]
+    #[should_panic(expected = "differ by NaN")]
+    fn test_assert_approx_differs_nan() {
+        f32::NAN.assert_approx_differs(1.0, 0.1);
+    }
+
+    #[test]
+    fn test_approx_scaled() {
+        let sum: f32 = core::iter::repeat_n(1.1f32, 1000).sum();
+        #[cfg(not(feature = "relative-default"))]
+        assert!(sum.approx(1100.0).not());
+        assert!(sum.approx_scaled(1100.0, 1000.0));
+        assert!(sum.approx_scaled(1100.0, 10.0).not());
+        assert!(1.0f64.approx_scaled(1.0000001, 1.0));
+        assert!(1.0f64.approx_scaled(1.0000001, 0.0).not());
+    }
+
+    #[test]
+    fn test_approx_within_percent() {
+        assert!(100.0f64.approx_within_percent(101.0, 2.0));
+        assert!(100.0f64.approx_within_percent(101.0, 1.0));
+        assert!(100.0f64.approx_within_percent(101.0, 0.5).not());
+        assert!(100.0f64.approx_within_percent(150.0, 50.0));
+        assert!(100.0f64.approx_within_percent(250.0, 50.0).not());
+        assert!((-100.0f32).approx_within_percent(-95.0, 10.0));
+        assert!(1e-7f64.approx_within_percent(0.0, 1.0));
+        assert!(1e-3f64.approx_within_percent(0.0, 1.0).not());
+        assert!(f64::INFINITY.approx_within_percent(1e300, 100.0).not());
+        assert!(f64::NAN.approx_within_percent(1.0, 100.0).not());
+        // The percentage is of the second argument, so the result depends on the order.
+        assert_ne!(
+            100.0f64.approx_within_percent(110.0, 9.5),
+            110.0f64.approx_within_percent(100.0, 9.5)
+        );
+    }
+
+    #[test]
+    fn test_approx_angle() {
+        use core::f64::consts::{PI, TAU};
+        assert!(0.1f64.approx_angle(0.1 + TAU));
+        assert!(0.1f64.approx_angle(0.1 - TAU - TAU - TAU));
+        assert!(0.1f64.approx(0.1 + TAU).not());
+        assert!(PI.approx_angle(-PI));
+        assert!(1e-7f64.approx_angle(TAU - 1e-7));
+        assert!(1e-3f64.approx_angle(TAU - 1e-3).not());
+        assert!(0.0f64.approx_angle(PI).not());
+        assert!(f64::NAN.approx_angle(0.0).not());
+        assert!(0.0f32.approx_angle(core::f32::consts::TAU));
+        assert!(0.0f32.approx_angle(0.01).not());
+    }
+
+    #[test]
+    fn test_approx_angle_deg() {
+        assert!(0.0f64.approx_angle_deg(360.0));
+        assert!(359.99999f64.approx_angle_deg(0.00001));
+        assert!(180.0f64.approx_angle_deg(-180.0));
+        assert!(10.0f64.approx_angle_deg(370.0 - 720.0));
+        assert!(0.0f64.approx_angle_deg(1.0).not());
+        assert!(90.0f32.approx_angle_deg(-270.0));
+    }
+
+    #[test]
+    fn test_approx_extremes() {
+        for value in [
+            f64::MAX,
+            f64::MIN,
+            f64::MIN_POSITIVE,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+        ] {
+            assert!(value.approx(value));
+            assert!(value.approx_with_tolerance(value, 0.0));
+            assert!(value.approx_with_tolerance(value, f64::NAN));
+            assert!(value.approx_relative(value));
+            assert!(value.approx_combined(value));
+        }
+        assert!(f64::MAX.approx(f64::MIN).not());
+        assert!(f64::MAX.approx_with_tolerance(f64::MIN, f64::MAX).not());
+        assert!(f64::MAX.approx_relative(f64::MIN).not());
+        assert!(f64::MAX.approx_combined(-f64::MAX).not());
+        assert!(f32::MAX.approx(f32::MAX));
+        assert!(f32::MAX.approx(f32::MIN).not());
+        assert!(f32::MIN.approx_with_tolerance(f32::MIN, 0.0));
+        assert!(f64::NAN.approx(f64::NAN).not());
+        assert_eq!(f64::MAX.approx_distance(f64::MIN), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_approx_signed_zero() {
+        assert!(0.0f64.approx(-0.0));
+        assert!(0.0f64.approx_signed_zero(-0.0).not());
+        assert!((-0.0f64).approx_signed_zero(0.0).not());
+        assert!(0.0f64.approx_signed_zero(0.0));
+        assert!((-0.0f32).approx_signed_zero(-0.0));
+        assert!(0.0f64.approx_signed_zero(1e-9));
+        assert!((-0.0f64).approx_signed_zero(1e-9));
+        assert!(1.0f64.approx_signed_zero(1.0000001));
+        assert!(f64::NAN.approx_signed_zero(f64::NAN).not());
+        let (above, below) = (0.0f64.atan2(-1.0), (-0.0f64).atan2(-1.0));
+        assert!(above.approx(below).not());
+    }
+
+    #[test]
+    fn test_approx_subnormal() {
+        let subnormal = f64::MIN_POSITIVE / 2.0;
+        assert!(subnormal.is_subnormal());
+        assert!(subnormal.approx(0.0));
+        assert!(subnormal.approx(-0.0));
+        assert!(subnormal.approx(-subnormal));
+        assert!(subnormal.approx(f64::from_bits(1)));
+        assert!(subnormal.approx(f64::MIN_POSITIVE));
+        assert!(subnormal.approx_with_tolerance(f64::MIN_POSITIVE, f64::MIN_POSITIVE));
+        assert!(subnormal.approx_ulps(subnormal, 0));
+        assert!(subnormal
+            .approx_ulps(f64::from_bits(subnormal.to_bits() + 1), 0)
+            .not());
+        assert!(subnormal.approx_ulps(0.0, 1).not());
+        assert!((f32::MIN_POSITIVE / 2.0).approx(0.0));
+    }
+
+    #[test]
+    fn test_approx_ulps() {
+        assert!(1.0f32.approx_ulps(1.0f32.next_up(), 1));
+        assert!(1.0f32.approx_ulps(1.0f32.next_up().next_up(), 1).not());
+        assert!(1.0f64.approx_ulps(1.0f64.next_down(), 1));
+        assert!(1.0f64.approx_ulps(f64::from_bits(1.0f64.to_bits() + 4), 4));
+        assert!(1.0f64
+            .approx_ulps(f64::from_bits(1.0f64.to_bits() + 5), 4)
+            .not());
+
+        assert!(0.0f64.approx_ulps(-0.0, 0));
+        let tiny = f64::from_bits(1);
+        assert!(tiny.approx_ulps(-tiny, 2));
+        assert!(tiny.approx_ulps(-tiny, 1).not());
+        assert!((-1.0f64).approx_ulps(1.0, u32::MAX).not());
+        assert!(f64::NAN.approx_ulps(f64::NAN, u32::MAX).not());
+    }
+
+    #[test]
+    fn test_approx_integer() {
+        assert!(3i32.approx(3));
+        assert!(3i32.approx(4).not());
+        assert!(3u64.approx_with_tolerance(5, 2));
+        assert!(5u64.approx_with_tolerance(3, 1).not());
+        assert!(i8::MIN.approx_with_tolerance(i8::MAX, i8::MAX).not());
+        assert!((-2i64).approx_with_tolerance(2, 4));
+        assert!(2i64.approx_with_tolerance(2, -1).not());
+        assert!(u128::MAX.approx_with_tolerance(0, u128::MAX));
+        assert!([1usize, 2].approx_with_tolerance([2, 1], 1));
+    }
+
+    #[test]
+    fn test_approx_with_nan_eq() {
+        assert!(f64::NAN.approx(f64::NAN).not());
+        assert!(f64::NAN.approx_with_nan_eq(f64::NAN));
+        assert!(f32::NAN.approx_with_nan_eq(-f32::NAN));
+        assert!(f64::NAN.approx_with_nan_eq(1.0).not());
+        assert!(1.0f64.approx_with_nan_eq(f64::NAN).not());
+        assert!(f64::INFINITY.approx_with_nan_eq(f64::INFINITY));
+        assert!(f64::INFINITY.approx_with_nan_eq(f64::NEG_INFINITY).not());
+        assert!(1.0f32.approx_with_nan_eq(1.0001));
+    }
+
+    #[test]
+    fn test_try_approx() {
+        assert_eq!(1.0f64.try_approx(1.0000001), Ok(()));
+        assert_eq!(
+            2.5f64.try_approx(2.0),
+            Err(ReportedMismatch::Values {
+                left: 2.5,
+                right: 2.0,
+                diff: 0.5,
+                relative: 0.25,
+                tolerance: 1e-6,
+                index: None,
+            })
+        );
+        assert!(matches!(
+            1.0f32.try_approx(-2.0),
+            Err(ReportedMismatch::Values { relative, tolerance, .. }) if relative == 1.5 && tolerance == 1e-3
+        ));
+
+        assert_eq!([1.0f64, 2.0][..].try_approx(&[1.0, 2.0000001][..]), Ok(()));
+        assert_eq!(
+            [1.0f64, 2.0, 3.0].try_approx([1.5, 2.0, 1.0]),
+            Err(ReportedMismatch::Values {
+                left: 3.0,
+                right: 1.0,
+                diff: 2.0,
+                relative: 2.0,
+                tolerance: 1e-6,
+                index: Some(2),
+            })
+        );
+        assert!(matches!(
+            [1.0f64, 2.0, 3.0].try_approx([9.0, f64::NAN, 1.0]),
+            Err(ReportedMismatch::Values { index: Some(1), .. })
+        ));
+        assert_eq!(
+            [1.0f64, 2.0][..].try_approx(&[1.0][..]),
+            Err(ReportedMismatch::Lengths { left: 2, right: 1 })
+        );
+    }
+
+    #[test]
+    fn test_approx_distance() {
+        assert_eq!(1.0f64.approx_distance(1.5), 0.5);
+        assert_eq!(1.0f32.approx_distance(1.25), 0.25);
+        assert_eq!(f64::INFINITY.approx_distance(f64::INFINITY), 0.0);
+        assert!(f64::NAN.approx_distance(1.0).is_nan());
+        assert_eq!(3u8.approx_distance(250), 247.0);
+        assert_eq!((-3i32).approx_distance(4), 7.0);
+        assert_eq!(Duration::ZERO.approx_distance(Duration::ZERO), 0.0);
+        assert_eq!(Duration::ZERO.approx_distance(Duration::MAX), f64::INFINITY);
+
+        assert_eq!([1.0f64, 2.0, 3.0].approx_distance([1.25, 2.0, 2.5]), 0.5);
+        assert_eq!([1.0f64, 2.0][..].approx_distance(&[1.0][..]), f64::INFINITY);
+        assert_eq!([0.0f64; 0].approx_distance([]), 0.0);
+        assert!([1.0f64, 2.0].approx_distance([f64::NAN, 9.0]).is_nan());
+    }
+
+    #[test]
+    fn test_approx_diff() {
+        assert!(1.0f64.approx_diff(1.5).approx(0.5));
+        assert!(1.5f32.approx_diff(1.0).approx(0.5));
+        assert_eq!(f64::INFINITY.approx_diff(f64::INFINITY), 0.0);
+        assert_eq!(f64::INFINITY.approx_diff(1.0), f64::INFINITY);
+        assert!(f64::NAN.approx_diff(1.0).is_nan());
+
+        let a = [1.0f64, 2.0, 3.0];
+        assert!(a[..]
+            .approx_diff(&[1.0, 2.0032, 2.9999][..])
+            .approx(Some(0.0032)));
+        assert!([1.0f64, 2.0].approx_diff([1.0, f64::NAN]).unwrap().is_nan());
+        assert!([f64::NAN, 2.0].approx_diff([1.0, 5.0]).unwrap().is_nan());
+        assert_eq!(a[..].approx_diff(&a[..2]), None);
+        assert_eq!([0.0f64; 0].approx_diff([]), None);
+    }
+
+    #[test]
+    fn test_approx_cfg() {
+        let default = ApproxConfig::default();
+        #[cfg(not(feature = "relative-default"))]
+        for (a, b) in [
+            (1.0f64, 1.0000001),
+            (1.0, 1.1),
+            (1e9, 1e9 + 1.0),
+            (f64::NAN, f64::NAN),
+        ] {
+            assert_eq!(a.approx_cfg(b, &default), a.approx(b));
+        }
+
+        let cfg = ApproxConfig::default()
+            .abs_tol(Some(1e-9))
+            .max_ulps(Some(4));
+        assert!(1.0f64.approx_cfg(1.0 + 1e-10, &cfg));
+        assert!(1e9f64.approx_cfg(f64::from_bits(1e9f64.to_bits() + 4), &cfg));
+        assert!(1e9f64.approx_cfg(1e9 + 1.0, &cfg).not());
+
+        let relative = ApproxConfig::default().abs_tol(None).rel_tol(Some(1e-6f32));
+        assert!(1e6f32.approx_cfg(1e6 + 0.5, &relative));
+        assert!(1.0f32.approx_cfg(1.0001, &relative).not());
+        assert!(1.0f32.approx_cfg(f32::INFINITY, &relative).not());
+        assert!(f32::INFINITY.approx_cfg(f32::NEG_INFINITY, &relative).not());
+        assert!(f32::INFINITY.approx_cfg(f32::INFINITY, &relative));
+
+        let none = ApproxConfig::<f64>::default().abs_tol(None);
+        assert!(1.0f64.approx_cfg(1.0, &none));
+        assert!(1.0f64.approx_cfg(1.0000001, &none).not());
+
+        assert!(f64::NAN.approx_cfg(f64::NAN, &default.nan_equal(true)));
+        assert!(f64::NAN.approx_cfg(1.0, &default.nan_equal(true)).not());
+    }
+
+    #[test]
+    #[cfg(feature = "relative-default")]
+    fn test_approx_relative_default() {
+        assert!(1e9f64.approx(1e9 + 0.5));
+        assert!(1e9f64.approx(1e9 + 10.0).not());
+        assert!(1.0f64.approx(1.0000001));
+        assert!(0.0f64.approx(1e-5).not());
+        assert!(1e6f32.approx(1e6 + 1.0));
+        assert!([1e9f64, 1.0].approx([1e9 + 0.5, 1.0]));
+        assert!(f64::INFINITY.approx(f64::MAX).not());
+    }
+
+    #[test]
+    fn test_approx_accumulator() {
+        let mut acc = ApproxAccumulator::new();
+        for i in 0..1_000_000u32 {
+            let a = f64::from(i) * 1e-3;
+            acc.push(a, a + 1e-7);
+        }
+        assert_eq!(acc.len(), 1_000_000);
+        assert!(acc.holds());
+        assert!(acc.max_distance() < 1e-6);
+        assert_eq!(acc.finish(), Ok(()));
+
+        acc.push(1.0, 2.0);
+        acc.push(1.0, 1.5);
+        acc.push(1.0, 5.0);
+        assert!(acc.holds().not());
+        assert_eq!(acc.finish(), Err((1_000_000, 4.0)));
+        acc.push(f64::NAN, 1.0);
+        acc.push(1.0, 9.0);
+        assert!(acc.max_distance().is_nan());
+
+        assert!(ApproxAccumulator::<f32>::default().is_empty());
+        assert_eq!(ApproxAccumulator::<f32>::new().finish(), Ok(()));
+        let mut ints = ApproxAccumulator::new();
+        ints.push(3u8, 3);
+        ints.push(3, 7);
+        assert_eq!(ints.finish(), Err((1, 4.0)));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_approx_sorted() {
+        assert!(approx_sorted(&[3.0f64, 1.0, 2.0], &[1.0001, 2.0, 2.9999]).not());
+        assert!(approx_sorted(
+            &[3.0f64, 1.0, 2.0],
+            &[1.0000001, 2.0, 2.9999999]
+        ));
+        assert!(approx_sorted(&[3.0f32, 1.0, 2.0], &[1.0001, 2.0, 2.9999]));
+        assert!(approx_sorted(&[2.0f64, 1.0, 2.0], &[2.0, 2.0, 1.0]));
+        assert!(approx_sorted(&[2.0f64, 1.0, 1.0], &[2.0, 2.0, 1.0]).not());
+        assert!(approx_sorted(&[1.0f64, 2.0], &[1.0, 2.0, 3.0]).not());
+        assert!(approx_sorted::<f64>(&[], &[]));
+        assert!(approx_sorted(&[f64::NAN, 1.0], &[1.0, f64::NAN]).not());
+        assert!(approx_sorted(&[f64::NAN, 1.0, 0.0], &[0.0, 1.0, 2.0]).not());
+        assert!(approx_sorted(&[5u8, 3, 4], &[3, 4, 5]));
+    }
+
+    #[test]
+    #[cfg(feature = "backtrace")]
+    fn test_assert_approx_traced() {
+        1.0f64.assert_approx_traced(1.0000001);
+        let payload = std::panic::catch_unwind(|| [1.0f64, 2.0].assert_approx_traced([1.0, 2.5]))
+            .unwrap_err();
+        let message = payload.downcast_ref::<String>().unwrap();
+        assert!(message.starts_with("[1.0, 2.0] != [1.0, 2.5]\nbacktrace:\n"));
+        assert!(message.contains("test_assert_approx_traced"));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_with_tolerance_override() {
+        assert_eq!(f64::current_tolerance(), 1e-6);
+        let inside = with_tolerance_override(1e-2f64, || {
+            assert_eq!(f64::current_tolerance(), 1e-2);
+            assert!(1.0f64.approx(1.005));
+            assert!([1.0f64, 2.0].approx([1.005, 2.0]));
+            assert!(1.0f64.approx_with_tolerance(1.005, 1e-6).not());
+            assert!(1.0f64.approx_scaled(1.05, 10.0));
+            assert!(1.0f64.approx_scaled(1.2, 10.0).not());
+            assert!(1.0f32.approx(1.005).not());
+            with_tolerance_override(0.0f64, || assert!(1.0f64.approx(1.0000001).not()));
+            assert!(std::thread::spawn(|| 1.0f64.approx(1.005))
+                .join()
+                .unwrap()
+                .not());
+            1.0f64.approx(1.005)
+        });
+        assert!(inside);
+        assert!(1.0f64.approx(1.005).not());
+
+        let result =
+            std::panic::catch_unwind(|| with_tolerance_override(1.0f64, || panic!("inside")));
+        assert!(result.is_err());
+        assert_eq!(f64::current_tolerance(), 1e-6);
+    }
+
+    #[test]
+    fn test_approx_count_mismatches() {
+        let a = [1.0f64, 2.0, 3.0, 4.0];
+        assert_eq!(approx_count_mismatches(&a, &[1.0000001, 2.0, 3.0, 4.0]), 0);
+        assert_eq!(approx_count_mismatches(&a, &[1.0, 2.1, 3.0, 4.1]), 2);
+        assert_eq!(approx_count_mismatches(&a, &[f64::NAN; 4]), 4);
+        assert_eq!(approx_count_mismatches(&a, &a[..3]), usize::MAX);
+        assert_eq!(approx_count_mismatches::<u8>(&[], &[]), 0);
+        assert_eq!(
+            approx_count_mismatches(&[[1u8, 2], [3, 4]], &[[1, 2], [3, 5]]),
+            1
+        );
+    }
+
+    #[test]
+    fn test_approx_similarity_ratio() {
+        let image = [1u8, 2, 3, 4, 5];
+        assert_eq!(approx_similarity_ratio(&image, &[1, 2, 3, 4, 6]), 0.8);
+        assert_eq!(approx_similarity_ratio(&image, &[1, 2, 3, 5, 6]), 0.6);
+        assert!(approx_at_least(&image, &[1, 2, 3, 4, 6], 0.8));
+        assert!(approx_at_least(&image, &[1, 2, 3, 5, 6], 0.8).not());
+        assert!(approx_at_least(&image, &image, 1.0));
+
+        assert_eq!(
+            approx_similarity_ratio(&[1.0f64, 2.0], &[1.0000001, 2.5]),
+            0.5
+        );
+        assert_eq!(
+            approx_similarity_ratio(&[1.0f64, 2.0], &[1.0, 2.0, 3.0, 4.0]),
+            0.5
+        );
+        assert_eq!(approx_similarity_ratio::<f64>(&[], &[1.0]), 0.0);
+        assert_eq!(approx_similarity_ratio::<f64>(&[], &[]), 1.0);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_first_mismatch() {
+        let a = vec![
+            vec![(1.0f64, 2.0)],
+            vec![(3.0, 4.0)],
+            vec![(5.0, 6.0), (7.0, 8.0)],
+        ];
+        let mut b = a.clone();
+        assert_eq!(a.first_mismatch(&b), None);
+        b[2][0].1 = 6.1;
+        b[2][1].0 = 0.0;
+        assert_eq!(a.first_mismatch(&b).as_deref(), Some("[2][0].1"));
+        b[2].pop();
+        assert_eq!(a.first_mismatch(&b).as_deref(), Some("[2][0].1"));
+        b[2][0].1 = 6.0;
+        assert_eq!(a.first_mismatch(&b).as_deref(), Some("[2][1]"));
+
+        assert_eq!(1.0f64.first_mismatch(2.0).as_deref(), Some(""));
+        assert_eq!(1.0f64.first_mismatch(1.0000001), None);
+        assert_eq!(
+            [Some((1, [1.0f32, 2.0]))]
+                .first_mismatch([Some((1, [1.0, 2.5]))])
+                .as_deref(),
+            Some("[0].1[1]")
+        );
+        assert_eq!(
+            [Some(1.0f64)].first_mismatch([None]).as_deref(),
+            Some("[0]")
+        );
+        assert_eq!(
+            Box::new([1u8, 2])
+                .first_mismatch(Box::new([1, 3]))
+                .as_deref(),
+            Some("[1]")
+        );
+        assert_eq!(
+            <&[u8]>::first_mismatch(&&[1u8][..], &[][..]).as_deref(),
+            Some("[0]")
+        );
+    }
+
+    #[test]
+    fn test_approx_cross() {
+        let single: f32 = (1..=100).map(|i| 1.0 / i as f32).sum();
+        let double: f64 = (1..=100).map(|i| 1.0 / f64::from(i)).sum();
+        assert!(single.approx_cross(double, 1e-5));
+        assert!(double.approx_cross(single, 1e-5));
+        assert!(single.approx_cross(double, 1e-9).not());
+        assert!(0.1f32.approx_cross(0.1, 1e-8));
+        assert!(0.1f32.approx_cross(0.1, 1e-10).not());
+        assert!(f32::INFINITY.approx_cross(f64::INFINITY, 0.0));
+        assert!(f32::NAN.approx_cross(f64::NAN, 1.0).not());
+    }
+
+    #[test]
+    fn test_approx_monotonic_increasing() {
+        let residuals = [1.0f64, 1.5, 1.75, 1.7499995, 1.875, 1.9375];
+        assert!(approx_monotonic_increasing(&residuals, 1e-6));
+        assert!(approx_monotonic_increasing(&residuals, 1e-7).not());
+        assert!(approx_monotonic_increasing(&[3.0f64, 2.0, 1.0], 1e-6).not());
+        assert!(approx_monotonic_increasing(&[1.0f64, f64::NAN, 2.0], 1.0).not());
+        assert!(approx_monotonic_increasing::<f32>(&[], 0.0));
+        assert!(approx_monotonic_increasing(&[1.0f32], 0.0));
+        assert!(approx_monotonic_increasing(&[1u32, 3, 2, 4], 1));
+        assert!(approx_monotonic_increasing(&[1u32, 3, 1, 4], 1).not());
+    }
+
+    #[test]
+    fn test_approx_by() {
+        let within_percent = |a: &f64, b: &f64| (a - b).abs() <= a.abs() / 100.0;
+        assert!(200.0f64.approx_by(201.5, within_percent));
+        assert!(200.0f64.approx_by(203.0, within_percent).not());
+        let same_length = |a: &[f32], b: &[f32]| a.len() == b.len();
+        assert!([1.0f32, 2.0][..].approx_by(&[5.0, 6.0][..], same_length));
+        assert!((1, 2.0f64).approx_by((1, 9.0), |a, b| a.0 == b.0));
+        assert!(1.0f64.approx_by(1.0, |_, _| false).not());
+    }
+
+    #[test]
+    fn test_approx_non_zero() {
+        let a = NonZeroU64::new(1_000).unwrap();
+        let b = NonZeroU64::new(1_003).unwrap();
+        assert!(a.approx(a));
+        assert!(a.approx(b).not());
+        assert!(a.approx_with_tolerance(b, 3));
+        assert!(a.approx_with_tolerance(b, 2).not());
+        assert_eq!(a.approx_distance(b), 3.0);
+        assert!(Some(NonZeroI32::new(-5).unwrap()).approx_with_tolerance(NonZeroI32::new(5), 10));
+    }
+
+    #[test]
+    fn test_approx_dyn() {
+        assert!(1.0f64.approx_dyn(&1.0000001));
+        assert!([1.0f32, 2.0][..].approx_dyn(&[1.0, 2.5][..]).not());
+
+        let expected: [&dyn DynApproxEq; 4] = [&1.0f64, &[1u8, 2], &(1, 2.0f32), &Some(0.5f64)];
+        let actual: [&dyn DynApproxEq; 4] = [&1.0000001f64, &[1u8, 2], &(1, 2.5f32), &Some(0.5f64)];
+        let matches = expected.map(|a| actual.iter().any(|b| a.dyn_approx(*b)));
+        assert_eq!(matches, [true, true, false, true]);
+        assert!(1.0f64.dyn_approx(&1.0f32).not());
+        assert!(1.0f64.as_any().is::<f64>());
+    }
+
+    #[test]
+    fn test_approx_bool() {
+        assert!(true.approx(true));
+        assert!(false.approx(false));
+        assert!(true.approx(false).not());
+        assert!(false.approx_with_tolerance(true, ()).not());
+        assert!((true, 1.0f64).approx((true, 1.0000001)));
+        assert!((true, 1.0f64).approx((false, 1.0)).not());
+        assert_eq!(true.approx_distance(false), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_approx_unit_and_phantom() {
+        assert!(().approx(()));
+        assert!(().approx_with_tolerance((), ()));
+        assert!(PhantomData::<str>.approx(PhantomData));
+        assert!((1.0f64, ()).approx((1.0000001, ())));
+    }
+
+    #[test]
+    fn test_approx_tolerance_wrapper() {
+        let rows = [
+            (Tolerance::new(1.0f64, 1e-2), 1.005),
+            (Tolerance::new(100.0, 1.0), 100.9),
+            (Tolerance::new(0.5, 1e-9), 0.5),
+        ];
+        for (expected, actual) in rows {
+            assert!(expected.approx(Tolerance::new(actual, 0.0)));
+        }
+        assert!(Tolerance::new(1.0f64, 1e-2)
+            .approx(Tolerance::new(1.05, 0.0))
+            .not());
+        assert!(Tolerance::new(1.05f64, 0.0).approx(Tolerance::new(1.0, 0.1)));
+        assert!(Tolerance::new(10u32, 2).approx(Tolerance::new(12, 0)));
+        assert!(Tolerance::new(10u32, 2).approx(Tolerance::new(13, 0)).not());
+    }
+
+    #[test]
+    fn test_approx_tol() {
+        assert!(1.0_f64.tol(0.01).approx(1.005));
+        assert!(1.0_f64.tol(0.001).approx(1.005).not());
+        assert!(10i32.tol(3).approx(7));
+        // Either side's tolerance is enough, as for any other `Tolerance`.
+        assert!(1.0f64.tol(0.1).approx(1.05f64.tol(0.0)));
+        assert!(1.05f64.tol(0.0).approx(1.0f64.tol(0.1)));
+        assert!(1.05f64.tol(0.0).approx(1.0f64.tol(0.01)).not());
+        for actual in [1.005, 1.02] {
+            assert_eq!(
+                1.0f64.tol(0.01).approx(actual),
+                ApproxEq::approx(&1.0f64.tol(0.01), actual.tol(0.0))
+            );
+        }
+        1.0_f64.tol(0.01).assert_approx(0.995);
+        1.0_f64.tol(0.0).assert_approx(0.995.tol(0.01));
+    }
+
+    #[test]
+    #[should_panic(expected = "1.0 != 1.1 (tolerance 0.01)")]
+    fn test_approx_tol_panic() {
+        1.0_f64.tol(0.01).assert_approx(1.1);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_approx_cfg_serde() {
+        let cfg: ApproxConfig<f64> =
+            serde_json::from_str(r#"{ "abs_tol": 1e-4, "rel_tol": 1e-6 }"#).unwrap();
+        assert_eq!(
+            (cfg.abs_tol, cfg.rel_tol, cfg.max_ulps, cfg.nan_equal),
+            (Some(1e-4), Some(1e-6), None, false)
+        );
+        assert!(1.0f64.approx_cfg(1.00005, &cfg));
+
+        let partial: ApproxConfig<f32> =
+            serde_json::from_str(r#"{ "max_ulps": 4, "nan_equal": true }"#).unwrap();
+        assert_eq!(partial.abs_tol, Some(f32::TOLERANCE));
+        assert_eq!((partial.max_ulps, partial.nan_equal), (Some(4), true));
+
+        let json = serde_json::to_string(&cfg).unwrap();
+        let round_trip: ApproxConfig<f64> = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            (
+                round_trip.abs_tol,
+                round_trip.rel_tol,
+                round_trip.max_ulps,
+                round_trip.nan_equal
+            ),
+            (cfg.abs_tol, cfg.rel_tol, cfg.max_ulps, cfg.nan_equal)
+        );
+    }
+
+    #[test]
+    fn test_approx_ord() {
+        assert_eq!(1.0f64.approx_cmp(1.0000001), Ordering::Equal);
+        assert_eq!(1.0f64.approx_cmp(1.1), Ordering::Less);
+        assert_eq!(1.1f64.approx_cmp(1.0), Ordering::Greater);
+        assert!(1.0f32.approx_lt(1.01));
+        assert!(1.0f32.approx_lt(1.0001).not());
+        assert!(1.0001f32.approx_le(1.0));
+        assert!(1.01f32.approx_gt(1.0));
+        assert!(1.0f32.approx_gt(1.0001).not());
+        assert!(1.0f32.approx_ge(1.0001));
+        assert!(1.0f32.approx_ge(1.01).not());
+        assert_eq!(f64::NAN.approx_cmp(f64::INFINITY), Ordering::Greater);
+
+        // Approximate equality is not transitive, so neither is the ordering.
+        let (a, b, c) = (1.0f64, 1.0000006, 1.0000012);
+        assert_eq!(a.approx_cmp(b), Ordering::Equal);
+        assert_eq!(b.approx_cmp(c), Ordering::Equal);
+        assert_eq!(a.approx_cmp(c), Ordering::Less);
+        assert!(a.approx_le(b) && b.approx_ge(a));
+        assert!(a.approx_lt(c) && c.approx_gt(a));
+    }
+
+    #[test]
+    fn test_approx_infinity() {
+        assert!(f64::INFINITY.approx(f64::INFINITY));
+        assert!(f64::NEG_INFINITY.approx(f64::NEG_INFINITY));
+        assert!(f64::INFINITY.approx(f64::NEG_INFINITY).not());
+        assert!(f32::INFINITY.approx(f32::INFINITY));
+        assert!(f32::NEG_INFINITY.approx(f32::INFINITY).not());
+        for finite in [0.0f64, 1.0, -1.0, f64::MAX, f64::MIN] {
+            assert!(finite.approx(f64::INFINITY).not());
+            assert!(finite.approx(f64::NEG_INFINITY).not());
+            assert!(f64::INFINITY.approx(finite).not());
+            assert!(f64::NEG_INFINITY.approx(finite).not());
+        }
+        assert!(1.0f64
+            .approx_with_tolerance(f64::INFINITY, f64::INFINITY)
+            .not());
+    }
+
+    #[test]
+    fn test_approx_duration() {
+        let a = Duration::from_millis(100);
+        let b = Duration::from_millis(100) + Duration::from_micros(500);
+        assert!(a.approx(b));
+        assert!(b.approx(a));
+        assert!(a.approx(Duration::from_millis(102)).not());
+        assert!(a.approx_with_tolerance(b, Duration::from_micros(100)).not());
+        assert!(Duration::ZERO.approx_with_tolerance(Duration::MAX, Duration::MAX));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_approx_elapsed() {
+        let start = Instant::now();
+        let later = start + Duration::from_millis(5);
+        assert!(start.approx(later));
+        assert!(later.approx(start));
+        assert!(start.approx(start + Duration::from_millis(20)).not());
+        assert!(approx_elapsed(start, later, Duration::from_millis(5)));
+        assert!(approx_elapsed(later, start, Duration::from_millis(4)).not());
+
+        let first = start.elapsed();
+        let second = start.elapsed();
+        assert!(first.approx_with_tolerance(second, Instant::TOLERANCE));
+    }
+
+    #[test]
+    fn test_approx_array() {
+        assert!([1.0f32; 3].approx([1.0001; 3]));
+        assert!([1.0f32, 2.0, 3.0].approx([1.0, 2.0, 3.01]).not());
+        assert!([1.0f64, 2.0].approx_with_tolerance([1.01, 2.01], 1e-1));
+        [0.5f64; 4].assert_approx([0.5000001; 4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "slices differ at index 1: 2.0 != 2.5 (1 of 2 elements differ)")]
+    fn test_assert_not_approx_array() {
+        [1.0f64, 2.0].assert_approx([1.0, 2.5]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    #[should_panic(expected = "slices differ at index 2: 3.0 != 3.1 (2 of 1000 elements differ)")]
+    fn test_assert_not_approx_vec() {
+        let a = vec![3.0f64; 1000];
+        let mut b = a.clone();
+        b[2] = 3.1;
+        b[999] = 0.0;
+        a.assert_approx(b);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_approx_nested_vec() {
+        let a = vec![vec![1.0f64, 2.0], vec![3.0, 4.0]];
+        assert!(a.approx(vec![vec![1.0000001, 2.0], vec![3.0, 4.0]]));
+        assert!(a.approx(vec![vec![1.0, 2.0], vec![3.0, 4.0, 5.0]]).not());
+        assert!(a.approx(vec![vec![1.0, 2.0], vec![3.0]]).not());
+        assert!(a.approx(vec![vec![1.0, 2.0], vec![3.0, 4.01]]).not());
+        assert!(a.approx_with_tolerance(vec![vec![1.0, 2.0], vec![3.0, 4.01]], 0.1));
+        assert_eq!(
+            a.first_mismatch_index(vec![vec![1.0, 2.0], vec![3.0, 4.01]]),
+            Some(1)
+        );
+        assert_eq!(a[1].first_mismatch_index(vec![3.0, 4.01]), Some(1));
+        assert_eq!(a[1].first_mismatch_index(vec![3.0]), Some(1));
+        assert_eq!(a.first_mismatch_index(&a), None);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    #[should_panic(
+        expected = "slices differ at index (1, 0): [3.0, 4.0] != [3.5, 4.0] (1 of 2 elements differ)"
+    )]
+    fn test_assert_not_approx_nested_vec() {
+        vec![vec![1.0f64, 2.0], vec![3.0, 4.0]].assert_approx(vec![vec![1.0, 2.0], vec![3.5, 4.0]]);
+    }
+
+    #[test]
+    fn test_approx_matrix() {
+        let identity = [[1.0f64, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+        let mut m = identity;
+        m[1][2] = 1e-7;
+        assert!(identity.approx(m));
+        m[2][1] = 1e-3;
+        assert!(identity.approx(m).not());
+        assert_eq!(identity.first_mismatch_index(m), Some(2));
+        assert_eq!(identity[2].first_mismatch_index(m[2]), Some(1));
+        assert!(identity.approx_with_tolerance(m, 1e-2));
+        let transform = [
+            [1.0f32, 0.0, 0.0, 5.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ];
+        let mut moved = transform;
+        moved[0][3] = 5.0005;
+        assert!(transform.approx(moved));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "slices differ at index (2, 1): [0.0, 0.0, 1.0] != [0.0, 1.001, 1.0] (1 of 3 elements differ)"
+    )]
+    fn test_assert_not_approx_matrix() {
+        let identity = [[1.0f64, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+        let mut m = identity;
+        m[2][1] = 1.001;
+        identity.assert_approx(m);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    #[should_panic(
+        expected = "slices differ at index (0, 2): [1.0, 2.0, 3.0] != [1.0, 2.0] (1 of 1 elements differ)"
+    )]
+    fn test_assert_not_approx_jagged_vec() {
+        vec![vec![1.0f64, 2.0, 3.0]].assert_approx(vec![vec![1.0, 2.0]]);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "slices differ at index 0: 1.0 != 1.5 (2 of 3 elements differ), max distance 2.5e0"
+    )]
+    fn test_assert_not_approx_slice_distance() {
+        [1.0f64, 2.0, 3.0][..].assert_approx(&[1.5, 2.0, 0.5][..]);
+    }
+
+    #[test]
+    #[should_panic(expected = "[1.0, 2.0] != [1.0] (lengths 2 and 1)")]
+    fn test_assert_not_approx_slice_len() {
+        [1.0f64, 2.0][..].assert_approx(&[1.0][..]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_approx_vec() {
+        let a = vec![1.0f64, 2.0, 3.0];
+        assert!(a.approx(vec![1.0000001, 2.0, 3.0]));
+        assert!(a.approx(&a));
+        assert!(a.approx(vec![1.0, 2.0]).not());
+        assert!(a.approx(vec![1.0, 2.0, 3.1]).not());
+        assert!(a.approx_with_tolerance(vec![1.0, 2.0, 3.1], 0.2));
+    }
+
+    #[test]
+    fn test_approx_all() {
+        let squares = (1..=4).map(|i| f64::from(i) * f64::from(i));
+        let sums = (1..=4).map(|i| (0..i).map(|j| f64::from(2 * j + 1)).sum::<f64>() + 1e-8);
+        assert!(approx_all(squares.clone(), sums.clone()));
+        assert!(approx_all(squares.clone(), sums.clone().take(3)).not());
+        assert!(approx_all(squares.map(|x| x + 0.1), sums).not());
+        assert!(approx_all(core::iter::empty::<f32>(), []));
+    }
+
+    #[test]
+    fn test_max_relative_error() {
+        let (index, error) =
+            max_relative_error(&[1.0f64, 200.0, 3.0], &[1.0, 201.0, 3.003]).unwrap();
+        assert_eq!(index, 1);
+        assert!(error.approx(1.0 / 201.0));
+        let (index, error) = max_relative_error(&[0.0f32, 1e-8], &[0.0, 2e-8]).unwrap();
+        assert_eq!(index, 1);
+        assert!(error.approx(0.5));
+        assert_eq!(
+            max_relative_error(&[1.0f64, f64::INFINITY], &[1.0, 1.0]),
+            Some((1, f64::INFINITY))
+        );
+        assert!(max_relative_error(&[f64::NAN, 1.0], &[1.0, 2.0])
+            .unwrap()
+            .1
+            .is_nan());
+        assert_eq!(max_relative_error(&[1.0f64], &[1.0, 2.0]), None);
+        assert_eq!(max_relative_error::<f64>(&[], &[]), None);
+    }
+
+    #[test]
+    fn test_approx_prefix() {
+        let a = [1.0f32, 2.0, 3.0];
+        let b = [1.0001f32, 2.0, 3.0, 4.0];
+        assert!(a[..].approx(&b[..]).not());
+        assert!(a[..].approx_prefix(&b[..]));
+        assert!(b[..].approx_prefix(&a[..]));
+        assert!(a[..].approx_prefix(&[1.0, 2.1, 3.0, 4.0][..]).not());
+        assert!(a[..].approx_prefix(&[][..]));
+    }
+
+    #[test]
+    fn test_assert_approx_table() {
+        assert_approx_table![(1.0f64, 1.0000001, 1e-6)];
+        assert_approx_table![
+            (1.0f64, 1.05, 0.1),
+            (2.0f32, 2.0, 0.0),
+            (10u8, 12, 2),
+            (
+                Duration::from_millis(5),
+                Duration::from_millis(6),
+                Duration::from_millis(1)
+            ),
+        ];
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion `2.0 + 0.5 approx 3.0` failed in row 2\n  left: 2.5\n right: 3.0\n   tol: 0.1"
+    )]
+    fn test_assert_not_approx_table() {
+        assert_approx_table![
+            (1.0f64, 1.0, 0.1),
+            (2.0, 2.01, 0.1),
+            (2.0 + 0.5, 3.0, 0.1),
+            (0.0, 9.0, 0.1),
+        ];
+    }
+
+    #[test]
+    #[cfg_attr(debug_assertions, should_panic(expected = "1.0 != 1.1"))]
+    fn test_debug_assert_approx() {
+        1.0f64.debug_assert_approx(1.0000001);
+        1.0f64.debug_assert_approx(1.1);
+    }
+
+    #[test]
+    fn test_approx_range() {
+        assert!((0.0f64..1.0).approx(0.0..1.0000001));
+        assert!((0.0f64..1.0).approx(0.0..1.0001).not());
+        assert!((0.0f64..1.0).approx_with_tolerance(0.0..1.0001, 1e-3));
+        assert!((0.0f32..=1.0).approx(0.0001..=1.0));
+        assert!((0.0f32..=1.0).approx(0.01..=1.0).not());
+        assert!((1u8..=4).approx_with_tolerance(2..=5, 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "0.0..1.0 != 0.0..1.0001 (end differs)")]
+    fn test_assert_not_approx_range_end() {
+        (0.0f64..1.0).assert_approx(0.0..1.0001);
+    }
+
+    #[test]
+    #[should_panic(expected = "0.0..=1.0 != 0.5..=1.0 (start differs)")]
+    fn test_assert_not_approx_range_start() {
+        (0.0f64..=1.0).assert_approx(0.5..=1.0);
+    }
+
+    #[test]
+    #[cfg(target_has_atomic = "32")]
+    fn test_approx_atomic() {
+        use core::sync::atomic::{AtomicU32, Ordering::SeqCst};
+        let a = AtomicU32::new(100);
+        let b = AtomicU32::new(100);
+        assert!(a.approx(&b));
+        b.fetch_add(3, SeqCst);
+        assert!(a.approx(&b).not());
+        assert!(approx_atomic(&a, &b, 3));
+        assert!(approx_atomic(&b, &a, 2).not());
+        assert_eq!(b.load_seq_cst(), 103);
+    }
+
+    #[test]
+    fn test_approx_cell() {
+        let a = Cell::new(1.0f64);
+        assert!(a.approx(Cell::new(1.0000001)));
+        a.set(1.1);
+        assert!(a.approx(Cell::new(1.0)).not());
+        assert!(a.approx_with_tolerance(Cell::new(1.0), 0.2));
+        let b = RefCell::new([1.0f64, 2.0]);
+        b.borrow_mut()[1] = 2.0000001;
+        assert!(b.approx(RefCell::new([1.0, 2.0])));
+        assert!(b.approx(&b));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_approx_ref_cell_borrowed() {
+        let a = RefCell::new(1.0f64);
+        let _guard = a.borrow_mut();
+        a.approx(RefCell::new(1.0));
+    }
+
+    #[test]
+    fn test_approx_wrapping() {
+        assert!(Wrapping(5i32).approx(Wrapping(5)));
+        assert!(Wrapping(5i32).approx(Wrapping(6)).not());
+        assert!(Wrapping(5i32).approx_with_tolerance(Wrapping(7), 2));
+        assert!((Wrapping(i32::MAX) + Wrapping(1)).approx_with_tolerance(Wrapping(i32::MIN + 1), 1));
+        assert!(Saturating(250u8).approx_with_tolerance(Saturating(255), 5));
+        assert!(Saturating(1.0f64).approx(Saturating(1.0000001)));
+    }
+
+    #[test]
+    fn test_approx_reverse() {
+        assert!(Reverse(1.0f64).approx(Reverse(1.0000001)));
+        assert!(Reverse(1.0f64).approx(Reverse(1.1)).not());
+        assert!(Reverse(10u8).approx_with_tolerance(Reverse(12), 2));
+        let mut a = [Reverse(2), Reverse(7), Reverse(1)];
+        a.sort();
+        assert!(a.map(|Reverse(x)| Reverse(f64::from(x))).approx([
+            Reverse(7.0),
+            Reverse(2.0),
+            Reverse(1.0000001)
+        ]));
+    }
+
+    #[test]
+    fn test_approx_reference() {
+        let (a, b) = (&1.0f64, &1.0000001f64);
+        assert!(<&f64>::approx(&a, b));
+        assert!(<&f64>::approx(&a, &1.1).not());
+        assert!(<&f64>::approx_with_tolerance(&a, &1.1, 0.2));
+        assert!([1.0f64, 2.0]
+            .iter()
+            .zip(&[1.0000001, 2.0])
+            .all(|(a, b)| ApproxEq::approx(&a, b)));
+        let slice: &[f32] = &[1.0, 2.0];
+        assert!(ApproxEq::approx(&slice, &[1.0001f32, 2.0][..]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_assert_not_approx_reference() {
+        <&f64>::assert_approx(&&1.0, &1.1);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_approx_pointer() {
+        let a = Box::new(1.0f64);
+        assert!(a.approx(Box::new(1.0000001)));
+        let far = Box::new(1.1);
+        assert!(a.approx(&far).not());
+        assert!(a.approx_with_tolerance(Box::new(1.1), 0.2));
+
+        let b = Arc::new(vec![1.0f32, 2.0]);
+        assert!(b.approx(Arc::new(vec![1.0001, 2.0])));
+        assert!(b.approx(Arc::new(vec![1.0, 2.1])).not());
+        assert!(Rc::new([1.0f64]).approx(Rc::new([1.0000001])));
+
+        let c: Box<[f64]> = Box::new([1.0, 2.0]);
+        assert!(c.approx(Box::<[f64]>::from([1.0, 2.0000001])));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_approx_cow() {
+        let owned: Cow<[f64]> = Cow::Owned(vec![1.0, 2.0, 3.0]);
+        let borrowed: Cow<[f64]> = Cow::Borrowed(&[1.0000001, 2.0, 3.0]);
+        assert!(owned.approx(&borrowed));
+        assert!(borrowed.approx(&owned));
+        assert!(owned.approx(Cow::Borrowed(&[1.0, 2.0][..])).not());
+        assert!(owned.approx_with_tolerance(Cow::Borrowed(&[1.1, 2.0, 3.0][..]), 0.2));
+    }
+
+    #[test]
+    fn test_approx_result() {
+        let ok: Result<f64, &str> = Ok(1.0);
+        assert!(ok.approx(Ok(1.0000001)));
+        assert!(ok.approx(Ok(1.1)).not());
+        assert!(ok.approx(Err("failed")).not());
+        assert!(Err::<f64, _>("failed").approx(Err("failed")));
+        assert!(Err::<f64, _>("failed").approx(Err("other")).not());
+        assert!(ok.approx_with_tolerance(Ok(1.1), 0.2));
+    }
+
+    #[test]
+    fn test_approx_tuple() {
+        assert!((1.0f64,).approx((1.0000001,)));
+        assert!((1.0f64, 2.0f32, Some(3.0f64)).approx((1.0000001, 2.0001, Some(3.0))));
+        assert!((1.0f64, 2.0f64, 3.0f64).approx((1.0, 2.1, 3.0)).not());
+        assert!((1.0f64, 2.0f32).approx_with_tolerance((1.01, 2.5), (1e-1, 1.0)));
+        assert!((1.0f64, 2.0f32)
+            .approx_with_tolerance((1.01, 2.5), (1e-1, 1e-1))
+            .not());
+
+        let a = (
+            1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0,
+        );
+        a.assert_approx(a);
+    }
+
+    #[test]
+    #[should_panic(expected = "(index 1 differs)")]
+    fn test_assert_not_approx_tuple() {
+        (1.0f64, 2.0f64, 3.0f64).assert_approx((1.0, 2.1, 3.0));
+    }
+
+    #[test]
+    #[cfg(feature = "simd")]
+    fn test_approx_simd_widths() {
+        assert!(f32x8::splat(1.0).approx(f32x8::splat(1.0001)));
+        assert!(f32x8::splat(1.0).approx(f32x8::splat(1.01)).not());
+        assert!(f32x16::splat(1.0).approx(f32x16::splat(1.0001)));
+        assert!(f32x16::splat(1.0).approx(f32x16::splat(1.01)).not());
+        assert!(f64x2::splat(1.0).approx(f64x2::splat(1.0000001)));
+        assert!(f64x2::splat(1.0).approx(f64x2::splat(1.00001)).not());
+        assert!(f64x8::splat(1.0).approx(f64x8::splat(1.0000001)));
+        assert!(f64x8::splat(1.0).approx(f64x8::splat(1.00001)).not());
+        assert!(f32x1::splat(1.0).approx(f32x1::splat(1.0001)));
+        assert!(f32x2::splat(1.0)
+            .approx(f32x2::from_array([1.0, 1.01]))
+            .not());
+        let mut lanes = [1.0f32; 32];
+        lanes[31] = 1.01;
+        assert!(f32x32::splat(1.0).approx(f32x32::splat(1.0001)));
+        assert!(f32x32::splat(1.0).approx(f32x32::from_array(lanes)).not());
+        assert!(f32x64::splat(1.0).approx(f32x64::splat(1.0001)));
+        let mut lanes = [1.0f64; 64];
+        lanes[63] = 1.00001;
+        assert!(f64x64::splat(1.0).approx(f64x64::splat(1.0000001)));
+        assert!(f64x64::splat(1.0).approx(f64x64::from_array(lanes)).not());
+        assert!(f64x16::splat(1.0)
+            .approx_any(f64x16::from_array([2.0; 16]))
+            .not());
+        assert!([1.0f64; 32].approx_simd([1.0000001; 32]));
+        assert!(f64x1::splat(f64::NAN).approx(f64x1::splat(f64::NAN)).not());
+        assert!(f32x4::splat(f32::INFINITY).approx(f32x4::splat(f32::INFINITY)));
+        assert!(f64x64::splat(f64::NEG_INFINITY).approx(f64x64::splat(f64::NEG_INFINITY)));
+        let lanes = f64x2::from_array([f64::INFINITY, 1.0]);
+        assert!(lanes.approx(lanes));
+        assert!(lanes
+            .approx(f64x2::from_array([f64::NEG_INFINITY, 1.0]))
+            .not());
+        assert!(f64x2::splat(f64::MAX)
+            .approx_with_tolerance(f64x2::splat(f64::INFINITY), f64x2::splat(f64::INFINITY))
+            .not());
+        assert!(lanes.approx_any(f64x2::from_array([f64::INFINITY, 2.0])));
+    }
+
+    #[test]
+    #[cfg(feature = "simd")]
+    fn test_approx_simd_lanes() {
+        let a = f32x4::from_array([1.0, 2.0, 3.0, 4.0]);
+        let b = f32x4::from_array([1.0, 2.5, 3.0, 4.0]);
+        // A lexicographic comparison would accept this pair because the first lane is within tolerance.
+        assert!(f32x4::from_array([0.0, 1.0, 0.0, 0.0])
+            .approx(f32x4::from_array([0.0, 0.0, 0.0, 0.0]))
+            .not());
+        assert!(a.approx(b).not());
+        assert!(a.approx_any(b));
+        assert!(a.approx_any(a + f32x4::splat(1.0)).not());
+        assert!(f64x2::from_array([1.0, 2.0]).approx_any(f64x2::from_array([5.0, 2.0])));
+    }
+
+    #[test]
+    #[cfg(feature = "simd")]
+    fn test_approx_array_simd() {
+        // Walk a deterministic sequence of offsets around the tolerance and compare with the scalar path.
+        let mut state = 0x2545_f491_u32;
+        let mut offset = || {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            (f64::from(state % 2001) - 1000.0) * 2e-6
+        };
+        for _ in 0..1000 {
+            let a: [f32; 4] = core::array::from_fn(|i| i as f32);
+            let b = a.map(|x| x + offset() as f32);
+            assert_eq!(a.approx_simd(b), a.approx(b));
+            let a: [f32; 8] = core::array::from_fn(|i| i as f32 / 2.0);
+            let b = a.map(|x| x + offset() as f32);
+            assert_eq!(a.approx_simd(b), a.approx(b));
+            let a = [1.0f64, -2.0, 3.0, 4.0];
+            let b = a.map(|x| x + offset() / 1e3);
+            assert_eq!(a.approx_simd(b), a.approx(b));
+        }
+        assert!([1.0f32; 16].approx_simd([1.0001; 16]));
+        assert!([1.0f64, 2.0].approx_simd([1.0, 2.1]).not());
+        for (a, b) in [
+            ([f32::INFINITY; 4], [f32::INFINITY; 4]),
+            ([f32::INFINITY; 4], [f32::NEG_INFINITY; 4]),
+            ([f32::INFINITY, 1.0, 2.0, 3.0], [f32::MAX, 1.0, 2.0, 3.0]),
+            ([1e9; 4], [1e9 + 64.0; 4]),
+        ] {
+            assert_eq!(a.approx_simd(b), a.approx(b));
+        }
+        #[cfg(feature = "std")]
+        with_tolerance_override(0.1f32, || {
+            assert!([1.0f32; 4].approx_simd([1.05; 4]));
+            assert_eq!(
+                [1.0f32; 4].approx_simd([1.2; 4]),
+                [1.0f32; 4].approx([1.2; 4])
+            );
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "simd")]
+    fn test_approx_simd_lane_tolerance() {
+        let a = f32x4::from_array([1.0, 2.0, 3.0, 1.0]);
+        let tol = f32x4::from_array([0.5, 1e-3, 1e-3, 1e-3]);
+        assert!(a.approx_with_lane_tolerance(f32x4::from_array([1.4, 2.0, 3.0, 1.0]), tol));
+        assert!(a
+            .approx_with_lane_tolerance(f32x4::from_array([1.0, 2.4, 3.0, 1.0]), tol)
+            .not());
+        assert!(a
+            .approx_with_lane_tolerance(f32x4::from_array([1.6, 2.0, 3.0, 1.0]), tol)
+            .not());
+        let b = f64x4::from_array([0.0, 10.0, 0.0, 0.0]);
+        let tol = f64x4::from_array([1e-9, 1.0, 1e-9, 1e-9]);
+        assert!(b.approx_with_lane_tolerance(f64x4::from_array([0.0, 10.9, 0.0, 0.0]), tol));
+        assert!(b
+            .approx_with_lane_tolerance(f64x4::from_array([1e-6, 10.0, 0.0, 0.0]), tol)
+            .not());
     }
 
     #[test]
@@ -0,0 +1,58 @@
+//! Numeric comparisons of strings, such as values read from CSV or configuration files.
+use crate::ApproxEq;
+
+/// Compare strings by the numbers they hold rather than by their text.
+pub trait ApproxNumericStr {
+    /// Parse both strings as [`f64`] and evaluate if the numbers are approximately equal.
+    ///
+    /// Parsing uses [`str::parse`], which accepts an optional sign, decimal digits with a `.` separator
+    /// and an optional `e` exponent, as well as `inf` and `NaN`, independent of the locale.
+    /// Thousands separators, a `,` as the decimal separator and surrounding whitespace are rejected.
+    /// Returns `false` if either string is not a number.
+    ///
+    /// ```
+    /// use approximately::ApproxNumericStr;
+    ///
+    /// assert!("1.0000".approx_numeric_str("1.00000001"));
+    /// assert!("1.5e3".approx_numeric_str("1500"));
+    /// assert!(!"1,5".approx_numeric_str("1.5"));
+    /// ```
+    fn approx_numeric_str(&self, other: &str) -> bool;
+}
+
+impl ApproxNumericStr for str {
+    fn approx_numeric_str(&self, other: &str) -> bool {
+        match (self.parse::<f64>(), other.parse::<f64>()) {
+            (Ok(a), Ok(b)) => a.approx(b),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::ops::Not;
+    use std::{borrow::Cow, string::String};
+
+    #[test]
+    fn test_approx_numeric_str() {
+        assert!("1.0000".approx_numeric_str("1"));
+        assert!("1.0000".approx_numeric_str("1.0000001"));
+        assert!("1.0000".approx_numeric_str("1.0001").not());
+        assert!("2.50".approx_numeric_str("2.5"));
+        assert!("1e-3".approx_numeric_str("0.001"));
+        assert!("1.2E+4".approx_numeric_str("12000.0000001"));
+        assert!("-0".approx_numeric_str("0.0"));
+        assert!("inf".approx_numeric_str("inf"));
+        assert!("NaN".approx_numeric_str("NaN").not());
+        assert!("abc".approx_numeric_str("abc").not());
+        assert!(" 1.0".approx_numeric_str("1.0").not());
+        assert!("".approx_numeric_str("0").not());
+
+        let owned = String::from("3.14159");
+        let cow: Cow<str> = Cow::Borrowed("3.1415900");
+        assert!(owned.approx_numeric_str(&cow));
+        assert!(cow.approx_numeric_str("3.1416").not());
+    }
+}
@@ -0,0 +1,79 @@
+//! Helpers for property-based tests, such as with `proptest` or `quickcheck`.
+use crate::ApproxEq;
+use alloc::{format, string::String};
+
+/// Check that the values are approximately equal, returning a description of the mismatch instead of panicking.
+///
+/// The error contains the debug values and, when it is finite, the [`ApproxEq::approx_distance`],
+/// such as the absolute difference of floats, so it composes with `proptest`'s `prop_assert!`
+/// and lets the shrinker report the error it minimized.
+/// Inside `proptest!`, the body of a property below would read `prop_assert!(prop_assert_approx(..).is_ok())`:
+///
+/// ```
+/// use approximately::prop_assert_approx;
+///
+/// let property = |a: f64| prop_assert_approx(&(a + a), &(2.0 * a));
+/// for a in [0.0, 0.25, 1e-3, 0.999] {
+///     assert_eq!(property(a), Ok(()));
+/// }
+/// assert_eq!(prop_assert_approx(&1.0f64, &1.5), Err("1.0 != 1.5 (difference 5e-1)".to_string()));
+/// ```
+pub fn prop_assert_approx<A: ApproxEq + ?Sized>(a: &A, b: &A) -> Result<(), String> {
+    if a.approx(b) {
+        return Ok(());
+    }
+    let distance = a.approx_distance(b);
+    let diff = if distance.is_finite() {
+        format!(" (difference {distance:e})")
+    } else {
+        String::new()
+    };
+    Err(format!("{a:?} != {b:?}{diff}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_prop_assert_approx() {
+        assert_eq!(prop_assert_approx(&1.0f64, &1.0000001), Ok(()));
+        assert_eq!(
+            prop_assert_approx(&vec![1.0f32, 2.0], &vec![1.0, 2.0001]),
+            Ok(())
+        );
+        assert_eq!(prop_assert_approx(&(1u8, 2u8), &(1, 2)), Ok(()));
+    }
+
+    #[test]
+    fn test_prop_assert_not_approx() {
+        assert_eq!(
+            prop_assert_approx(&1.0f64, &1.5),
+            Err(String::from("1.0 != 1.5 (difference 5e-1)"))
+        );
+        assert_eq!(
+            prop_assert_approx(&2.0f32, &2.25),
+            Err(String::from("2.0 != 2.25 (difference 2.5e-1)"))
+        );
+        assert_eq!(
+            prop_assert_approx(&[1u8, 2], &[1, 3]),
+            Err(String::from("[1, 2] != [1, 3] (difference 1e0)"))
+        );
+        assert_eq!(
+            prop_assert_approx(&Some(1.0f64), &None),
+            Err(String::from("Some(1.0) != None"))
+        );
+    }
+
+    #[test]
+    fn test_prop_assert_approx_borrowed() {
+        let (a, b) = (vec![1.0f64, 2.0], vec![1.0, 2.5]);
+        let (a, b): (&[f64], &[f64]) = (&a, &b);
+        assert_eq!(prop_assert_approx(&a, &a), Ok(()));
+        assert_eq!(
+            prop_assert_approx(a, b),
+            Err(String::from("[1.0, 2.0] != [1.0, 2.5] (difference 5e-1)"))
+        );
+    }
+}
@@ -0,0 +1,126 @@
+//! Implementations for strings, compared by their edit distance, and for characters, compared by their code points.
+//!
+//! The distance is computed in `O(n * m)` time for strings of `n` and `m` characters,
+//! which is fine for fuzzy assertions in tests but not meant for production comparisons.
+use crate::{ApproxEq, ApproxTolerance};
+use alloc::{string::String, vec::Vec};
+use core::borrow::Borrow;
+
+/// Count the insertions, deletions and substitutions of characters needed to turn `a` into `b`.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, a) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, b) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if a == *b {
+                diagonal
+            } else {
+                1 + diagonal.min(above).min(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+impl ApproxEq for str {
+    fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+        self.approx_with_tolerance(other, Self::TOLERANCE)
+    }
+}
+
+/// Strings are approximately equal if their Levenshtein distance is at most the tolerance, one edit by default.
+impl ApproxTolerance for str {
+    type Tolerance = usize;
+    const TOLERANCE: usize = 1;
+    fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, max_distance: usize) -> bool {
+        let other = other.borrow();
+        if self == other {
+            return true;
+        }
+        // The distance is at least the difference of the lengths.
+        self.chars().count().abs_diff(other.chars().count()) <= max_distance
+            && levenshtein(self, other) <= max_distance
+    }
+}
+
+impl ApproxEq for String {
+    fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+        self.as_str().approx(other.borrow().as_str())
+    }
+}
+
+impl ApproxTolerance for String {
+    type Tolerance = usize;
+    const TOLERANCE: usize = str::TOLERANCE;
+    fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, max_distance: usize) -> bool {
+        self.as_str()
+            .approx_with_tolerance(other.borrow().as_str(), max_distance)
+    }
+}
+
+impl ApproxEq for char {
+    fn approx<T: Borrow<Self>>(&self, other: T) -> bool {
+        self.approx_with_tolerance(other, Self::TOLERANCE)
+    }
+}
+
+/// Characters are exactly equal by default, and `tol` is the largest allowed distance between their code points.
+impl ApproxTolerance for char {
+    type Tolerance = u32;
+    const TOLERANCE: u32 = 0;
+    fn approx_with_tolerance<T: Borrow<Self>>(&self, other: T, tol: u32) -> bool {
+        u32::from(*self).abs_diff(u32::from(*other.borrow())) <= tol
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::ops::Not;
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("", ""), 0);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("héllo", "hello"), 1);
+    }
+
+    #[test]
+    fn test_approx_str() {
+        assert!("hello".approx("hello"));
+        assert!("hello".approx("helo"));
+        assert!("hello".approx("hallo"));
+        assert!("hello".approx("hlelo").not());
+        assert!("kitten".approx_with_tolerance("sitting", 3));
+        assert!("kitten".approx_with_tolerance("sitting", 2).not());
+        assert!("a".approx_with_tolerance("abc", 1).not());
+    }
+
+    #[test]
+    fn test_approx_string() {
+        let a = String::from("approximately");
+        assert!(a.approx(String::from("aproximately")));
+        assert!(a.approx(String::from("approx")).not());
+        assert!(a.approx_with_tolerance(String::from("approx"), 7));
+    }
+
+    #[test]
+    fn test_approx_char() {
+        assert!('a'.approx('a'));
+        assert!('a'.approx('b').not());
+        assert!('a'.approx_with_tolerance('c', 2));
+        assert!('c'.approx_with_tolerance('a', 1).not());
+        assert!('é'.approx('e').not());
+    }
+
+    #[test]
+    #[should_panic(expected = "\"hello\" != \"world\"")]
+    fn test_assert_not_approx_string() {
+        String::from("hello").assert_approx(String::from("world"));
+    }
+}
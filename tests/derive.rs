@@ -0,0 +1,195 @@
+use approximately::ApproxEq;
+use std::{marker::PhantomData, ops::Not};
+
+#[derive(Debug, Clone, ApproxEq)]
+struct Position {
+    x: f64,
+    y: f64,
+    z: f32,
+}
+
+#[derive(Debug, Clone, ApproxEq)]
+struct Color(f32, f32, f32);
+
+#[derive(Debug, Clone, ApproxEq)]
+struct Tagged {
+    value: Vec<f64>,
+    #[approx(skip)]
+    name: String,
+}
+
+#[derive(Debug, Clone, ApproxEq)]
+struct Wrapper<T> {
+    inner: Option<T>,
+}
+
+#[derive(Debug, Clone, ApproxEq)]
+struct Unit;
+
+#[derive(Debug, Clone)]
+struct Meters;
+
+#[derive(Debug, Clone, ApproxEq)]
+struct Quantity<U> {
+    value: f64,
+    unit: PhantomData<U>,
+    marker: (),
+}
+
+#[derive(Debug, Clone, ApproxEq)]
+struct Switch {
+    enabled: bool,
+    level: f64,
+}
+
+const RATIO_TOLERANCE: f64 = 1e-9;
+
+#[derive(Debug, Clone, ApproxEq)]
+struct Reading {
+    #[approx(tolerance = 0.01)]
+    temperature: f64,
+    #[approx(tolerance = RATIO_TOLERANCE)]
+    ratio: f64,
+    pressure: f32,
+    #[approx(tolerance = 2)]
+    samples: u32,
+}
+
+#[test]
+fn test_derive_named() {
+    let a = Position {
+        x: 1.0,
+        y: 2.0,
+        z: 3.0,
+    };
+    assert!(a.approx(Position {
+        x: 1.0000001,
+        y: 2.0,
+        z: 3.0001
+    }));
+    assert!(a
+        .approx(Position {
+            x: 1.0,
+            y: 2.1,
+            z: 3.0
+        })
+        .not());
+}
+
+#[test]
+fn test_derive_tuple() {
+    let a = Color(0.5, 0.25, 1.0);
+    assert!(a.approx(Color(0.5001, 0.25, 1.0)));
+    assert!(a.approx(Color(0.5, 0.26, 1.0)).not());
+}
+
+#[test]
+fn test_derive_skip() {
+    let a = Tagged {
+        value: vec![1.0, 2.0],
+        name: "a".to_owned(),
+    };
+    assert!(a.approx(Tagged {
+        value: vec![1.0, 2.0000001],
+        name: "a".to_owned()
+    }));
+    assert!(a
+        .approx(Tagged {
+            value: vec![1.0, 2.0],
+            name: "b".to_owned()
+        })
+        .not());
+}
+
+#[test]
+fn test_derive_generic() {
+    assert!(Wrapper {
+        inner: Some(1.0f64)
+    }
+    .approx(Wrapper {
+        inner: Some(1.0000001)
+    }));
+    assert!(Wrapper::<f64> { inner: None }
+        .approx(Wrapper { inner: Some(1.0) })
+        .not());
+    assert!(Unit.approx(Unit));
+}
+
+#[test]
+fn test_derive_phantom() {
+    let a = Quantity::<Meters> {
+        value: 1.0,
+        unit: PhantomData,
+        marker: (),
+    };
+    assert!(a.approx(Quantity {
+        value: 1.0000001,
+        unit: PhantomData,
+        marker: (),
+    }));
+    assert!(a
+        .approx(Quantity {
+            value: 1.1,
+            unit: PhantomData,
+            marker: (),
+        })
+        .not());
+}
+
+#[test]
+fn test_derive_bool() {
+    let a = Switch {
+        enabled: true,
+        level: 0.5,
+    };
+    assert!(a.approx(Switch {
+        enabled: true,
+        level: 0.5000001,
+    }));
+    assert!(a
+        .approx(Switch {
+            enabled: false,
+            level: 0.5,
+        })
+        .not());
+}
+
+#[test]
+fn test_derive_tolerance() {
+    let a = Reading {
+        temperature: 20.0,
+        ratio: 0.5,
+        pressure: 1.0,
+        samples: 10,
+    };
+    assert!(a.approx(Reading {
+        temperature: 20.005,
+        ratio: 0.5,
+        pressure: 1.0001,
+        samples: 12,
+    }));
+    assert!(a
+        .approx(Reading {
+            temperature: 20.0,
+            ratio: 0.5 + 1e-8,
+            pressure: 1.0,
+            samples: 10,
+        })
+        .not());
+    assert!(a
+        .approx(Reading {
+            temperature: 20.0,
+            ratio: 0.5,
+            pressure: 1.01,
+            samples: 10,
+        })
+        .not());
+    assert!(a
+        .approx(Reading {
+            temperature: 20.0,
+            ratio: 0.5,
+            pressure: 1.0,
+            samples: 13,
+        })
+        .not());
+}
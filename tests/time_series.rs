@@ -0,0 +1,47 @@
+use approximately::ApproxEq;
+use std::{ops::Not, time::Duration};
+
+fn series(samples: &[(u64, f64)]) -> Vec<(Duration, f64)> {
+    samples
+        .iter()
+        .map(|&(micros, value)| (Duration::from_micros(micros), value))
+        .collect()
+}
+
+#[test]
+fn test_time_series_approx() {
+    let expected = series(&[(0, 0.0), (10_000, 0.5), (20_000, 1.0), (30_000, 0.5)]);
+    let measured = series(&[
+        (300, 1e-7),
+        (10_900, 0.5000004),
+        (19_500, 0.9999995),
+        (30_000, 0.5),
+    ]);
+    assert!(expected.approx(&measured));
+    expected.assert_approx(&measured);
+    assert_eq!(expected.first_mismatch_index(&measured), None);
+}
+
+#[test]
+fn test_time_series_late_sample() {
+    let expected = series(&[(0, 0.0), (10_000, 0.5), (20_000, 1.0)]);
+    let measured = series(&[(0, 0.0), (11_500, 0.5), (20_000, 1.0)]);
+    assert!(expected.approx(&measured).not());
+    assert_eq!(expected.first_mismatch_index(&measured), Some(1));
+}
+
+#[test]
+fn test_time_series_value_outside_tolerance() {
+    let expected = series(&[(0, 0.0), (10_000, 0.5), (20_000, 1.0), (30_000, 0.5)]);
+    let measured = series(&[(0, 0.0), (10_000, 0.5), (20_000, 1.0), (30_000, 0.51)]);
+    assert!(expected.approx(&measured).not());
+    assert_eq!(expected.first_mismatch_index(&measured), Some(3));
+}
+
+#[test]
+#[should_panic(expected = "slices differ at index 2")]
+fn test_time_series_assert_reports_index() {
+    let expected = series(&[(0, 0.0), (10_000, 0.5), (20_000, 1.0)]);
+    let measured = series(&[(0, 0.0), (10_000, 0.5), (20_000, 1.1)]);
+    expected.assert_approx(&measured);
+}